@@ -0,0 +1,1109 @@
+use std::io;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use wordle::{
+    AnswerSelection, Benchmark, DailyPuzzle, Dictionary, Difficulty, Game, GameConfig, GameStatus,
+    LetterKnowledge, MultiGame, Opponent, Palette, Play, Puzzle, SaveFile, SavedGame, Solver,
+    StatisticsFile, Word, WordParseError, WordScore,
+};
+
+use crate::terminal::TerminalStyle;
+
+mod protocol;
+mod terminal;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "tui")]
+mod tui;
+
+/// Guess the secret word in a few tries, with hints after every guess.
+#[derive(Debug, Parser)]
+#[command(
+    name = "wordle",
+    version,
+    about,
+    args_conflicts_with_subcommands = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    play: PlayArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Play a game, resuming the saved one unless another is chosen (the default)
+    Play(PlayArgs),
+    /// Play today's puzzle, the same for everyone
+    Daily(DailyArgs),
+    /// Get suggestions for a game played elsewhere, from each guess and its score
+    Solve(RulesArgs),
+    /// Show how all your games went
+    Stats,
+    /// Measure how well the solver plays every answer
+    Bench(BenchArgs),
+}
+
+/// The words a game is played with.
+#[derive(Debug, Args)]
+struct WordListArgs {
+    /// Play with words of this many letters
+    #[arg(long, value_name = "NUMBER", value_parser = parse_length)]
+    length: Option<usize>,
+    /// Play with the words in this file, one per line, instead of the built-in word list
+    #[arg(long, value_name = "PATH", conflicts_with = "length")]
+    dictionary: Option<PathBuf>,
+}
+
+/// The rules a game is played by.
+#[derive(Debug, Args)]
+struct RulesArgs {
+    /// Letters revealed by hints must be used in later guesses
+    #[arg(long)]
+    hard: bool,
+    /// Number of guesses, or "unlimited"
+    #[arg(long, value_name = "NUMBER", value_parser = parse_guesses)]
+    guesses: Option<MaximumGuesses>,
+    #[command(flatten)]
+    word_list: WordListArgs,
+}
+
+/// How a game is shown.
+#[derive(Debug, Args)]
+struct DisplayArgs {
+    /// Color tiles orange and blue instead of green and yellow
+    #[arg(long)]
+    high_contrast: bool,
+    /// Play full-screen with an on-screen keyboard
+    #[arg(long)]
+    tui: bool,
+}
+
+#[derive(Debug, Args)]
+struct PlayArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+    #[command(flatten)]
+    display: DisplayArgs,
+    /// Play with this secret word
+    #[arg(long, conflicts_with_all = ["seed", "absurd"])]
+    word: Option<String>,
+    /// Play the game chosen by this number, e.g. to replay it
+    #[arg(long, value_name = "NUMBER", conflicts_with = "absurd")]
+    seed: Option<u64>,
+    /// The secret word changes to dodge your guesses, until only one is left
+    #[arg(long)]
+    absurd: bool,
+    /// Guess this many words at once, with one more guess per extra board
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        conflicts_with_all = ["word", "absurd", "new", "tui"]
+    )]
+    boards: Option<usize>,
+    /// Start a new game instead of resuming the saved one
+    #[arg(long)]
+    new: bool,
+    /// Play over a JSON-lines protocol, e.g. from a bot
+    ///
+    /// Each line read is a command, e.g. {"guess": "CRANE"} or {"new_game": {"word_length": 6}},
+    /// and each line written is the game or an error with a stable code. The game isn't saved.
+    #[arg(
+        long,
+        conflicts_with_all = ["word", "seed", "absurd", "boards", "new", "high_contrast", "tui"]
+    )]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+struct DailyArgs {
+    #[command(flatten)]
+    rules: RulesArgs,
+    #[command(flatten)]
+    display: DisplayArgs,
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// The solver's guesses must use the letters revealed by hints
+    #[arg(long)]
+    hard: bool,
+    #[command(flatten)]
+    word_list: WordListArgs,
+}
+
+/// The number of guesses given by "--guesses", `None` being unlimited.
+#[derive(Clone, Copy, Debug)]
+struct MaximumGuesses(Option<usize>);
+
+fn parse_guesses(s: &str) -> Result<MaximumGuesses, String> {
+    match s {
+        "unlimited" => Ok(MaximumGuesses(None)),
+        _ => match s.parse::<usize>() {
+            Ok(guesses) if guesses > 0 => Ok(MaximumGuesses(Some(guesses))),
+            _ => Err("expected a positive number or \"unlimited\"".to_string()),
+        },
+    }
+}
+
+fn parse_length(s: &str) -> Result<usize, String> {
+    let lengths: Vec<String> = Dictionary::builtin_lengths()
+        .map(|length| length.to_string())
+        .collect();
+    match s.parse::<usize>() {
+        Ok(length) if Dictionary::builtin(length).is_some() => Ok(length),
+        _ => Err(format!("available lengths are {}", lengths.join(", "))),
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command.unwrap_or(Command::Play(cli.play)) {
+        Command::Play(args) => play_command(&args),
+        Command::Daily(args) => {
+            let config = game_config(&args.rules);
+            print_banner(&config, &args.rules.word_list);
+
+            let puzzle = DailyPuzzle::today();
+            println!("Daily puzzle #{} ({}).", puzzle.number, puzzle.date);
+            start_game(config, &args.display, Some(AnswerSelection::Daily(puzzle)));
+        }
+        Command::Solve(rules) => {
+            let config = game_config(&rules);
+            print_banner(&config, &rules.word_list);
+
+            println!("Assistant mode: type each guess and its score, e.g. \"CRANE _X__O\".");
+            let solver = Solver::new(&config.dictionary);
+            print_suggestions(&solver, &[], config.difficulty);
+            assist_loop(&solver, &config);
+        }
+        Command::Stats => {
+            let Some(statistics_file) = StatisticsFile::default_location() else {
+                eprintln!("No data directory to read statistics from.");
+                std::process::exit(1);
+            };
+            match statistics_file.load() {
+                Ok(statistics) => println!("{}", statistics),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Bench(args) => {
+            let config = with_word_list(
+                GameConfig::default().with_difficulty(difficulty(args.hard)),
+                &args.word_list,
+            );
+            print_banner(&config, &args.word_list);
+
+            let dictionary = &config.dictionary;
+            let solver = Solver::new(dictionary);
+            println!("Playing {} answers...", dictionary.answers().len());
+            println!("{}", Benchmark::run(&solver, dictionary, config.difficulty));
+        }
+    }
+}
+
+/// Plays a game, or several boards at once, resuming the saved game unless another is chosen.
+fn play_command(args: &PlayArgs) {
+    let rules = &args.rules;
+    let config = game_config(rules);
+
+    if args.json {
+        json_loop(config);
+        return;
+    }
+    print_banner(&config, &rules.word_list);
+
+    if let Some(boards) = args.boards {
+        if !(1..=config.dictionary.answers().len()).contains(&boards) {
+            eprintln!("Invalid number of boards {:?}.", boards);
+            std::process::exit(2);
+        }
+
+        if boards > 1 {
+            let config = match args.seed {
+                Some(seed) => config.with_answer(AnswerSelection::Seeded(seed)),
+                None => config,
+            };
+            let game = match MultiGame::from_config(&config, boards) {
+                Ok(game) => game,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(2);
+                }
+            };
+            println!(
+                "{} boards: every guess is played on each board, with {} in all.",
+                boards,
+                describe_guesses(game.maximum_guesses)
+            );
+            let (palette, style) = display_style(&args.display);
+            session_loop(&config, None, palette, style, ConsoleGame::Multi(game));
+            return;
+        }
+    }
+
+    let answer = if args.absurd {
+        println!("Absurd mode: the secret word changes to dodge your guesses.");
+        Some(AnswerSelection::Adversarial)
+    } else if let Some(word) = &args.word {
+        match word.parse::<Word>() {
+            Ok(word) if word.length() == config.word_length() => Some(AnswerSelection::Word(word)),
+            Ok(_) => {
+                let err = WordParseError::WrongLength {
+                    expected: config.word_length(),
+                };
+                eprintln!("Invalid word {:?}: {}", word, err);
+                std::process::exit(2);
+            }
+            Err(err) => {
+                eprintln!("Invalid word {:?}: {}", word, err);
+                std::process::exit(2);
+            }
+        }
+    } else if let Some(seed) = args.seed {
+        Some(AnswerSelection::Seeded(seed))
+    } else if args.new
+        || rules.hard
+        || rules.guesses.is_some()
+        || rules.word_list.length.is_some()
+        || rules.word_list.dictionary.is_some()
+    {
+        Some(AnswerSelection::Random)
+    } else {
+        None
+    };
+
+    start_game(config, &args.display, answer);
+}
+
+/// Plays a game with the chosen answer, or else resumes the saved game if there is one.
+/// The game in progress is saved after every move, with its word list.
+fn start_game(config: GameConfig, display: &DisplayArgs, answer: Option<AnswerSelection>) {
+    let (palette, style) = display_style(display);
+    // The line-by-line console stays the default, e.g. for piped input
+    let tui = display.tui && io::stdin().is_terminal() && io::stdout().is_terminal();
+
+    let save_file = SaveFile::default_location();
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            if let Some(saved) = save_file.as_ref().and_then(load_saved_game) {
+                println!("Resuming your saved game. Pass --new to start a new one.");
+                if !tui {
+                    print_plays(&saved.game, style);
+                }
+                play(
+                    &saved.config,
+                    save_file.as_ref(),
+                    palette,
+                    style,
+                    tui,
+                    saved.game,
+                );
+                return;
+            }
+            AnswerSelection::Random
+        }
+    };
+    let config = config.with_answer(answer);
+
+    play(
+        &config,
+        save_file.as_ref(),
+        palette,
+        style,
+        tui,
+        Game::new(&config),
+    );
+}
+
+fn difficulty(hard: bool) -> Difficulty {
+    if hard {
+        Difficulty::Hard
+    } else {
+        Difficulty::Normal
+    }
+}
+
+/// The rules chosen by the arguments, with the built-in five-letter word list by default.
+fn game_config(rules: &RulesArgs) -> GameConfig {
+    let config = GameConfig::default().with_difficulty(difficulty(rules.hard));
+    let config = with_word_list(config, &rules.word_list);
+    match rules.guesses {
+        Some(MaximumGuesses(maximum_guesses)) => config.with_maximum_guesses(maximum_guesses),
+        None => config,
+    }
+}
+
+/// Switches to the word list chosen by the arguments, exiting if its file can't be loaded.
+fn with_word_list(config: GameConfig, word_list: &WordListArgs) -> GameConfig {
+    let config = match word_list.length {
+        Some(length) => config.with_word_length(length).unwrap(), // checked by parse_length
+        None => config,
+    };
+    match &word_list.dictionary {
+        Some(path) => match Dictionary::from_file(path) {
+            Ok(dictionary) => config.with_dictionary(dictionary),
+            Err(err) => {
+                eprintln!("Failed to load {}: {}", path.display(), err);
+                std::process::exit(2);
+            }
+        },
+        None => config,
+    }
+}
+
+/// Prints the title, and the rules which differ from the original game's.
+fn print_banner(config: &GameConfig, word_list: &WordListArgs) {
+    println!("WORDLE!");
+    println!("Type \"help\" for game rules.");
+    if config.difficulty == Difficulty::Hard {
+        println!("Hard mode: revealed hints must be used in subsequent guesses.");
+    }
+    if word_list.length.is_some() || word_list.dictionary.is_some() {
+        println!("Playing with {}-letter words.", config.word_length());
+    }
+}
+
+/// The palette chosen by the arguments, and how scores are drawn with it:
+/// as colored tiles, unless stdout isn't a terminal or NO_COLOR is set.
+fn display_style(display: &DisplayArgs) -> (Palette, TerminalStyle) {
+    let palette = if display.high_contrast {
+        Palette::HighContrast
+    } else {
+        Palette::Standard
+    };
+    (palette, TerminalStyle::detect(palette))
+}
+
+/// Reads the game saved by an earlier session, if it is still in progress.
+/// A save file which can't be read is reported, and replaced by the next save.
+fn load_saved_game(save_file: &SaveFile) -> Option<SavedGame> {
+    match save_file.load() {
+        Ok(saved) => saved.filter(|saved| saved.game.calculate_status() == GameStatus::Active),
+        Err(err) => {
+            eprintln!("{}\nStarting a new game.", err);
+            None
+        }
+    }
+}
+
+/// The game rules, for the number of letters and guesses of the game being played.
+fn help_message(word_length: usize, maximum_guesses: Option<usize>) -> String {
+    let guesses = match maximum_guesses {
+        Some(maximum_guesses) => format!("Make up to ({}) guesses.", maximum_guesses),
+        None => "Make as many guesses as you need.".to_string(),
+    };
+
+    format!(
+        r"Guess the secret word -- a random English word, {} letters long.
+Your game is saved after every guess and resumed next time, unless you pass --new.
+Run wordle stats to see how all your games went.
+Run wordle --help for all the ways to play, e.g. wordle daily or wordle --length 6.
+Set NO_COLOR to see scores as letters instead of colored tiles.
+
+{}
+Type 'give up' to see the word (it counts as a loss), 'new' to start another game,
+or 'quit' to stop for now.
+
+An 'X' under a letter means you guessed the right letter in the right spot.
+An 'O' means the letter you guessed there is in the word, but somewhere else.
+An '_' means the letter you guessed there isn't in the word.
+
+In hard mode (--hard), letters marked 'X' must stay in place in later guesses,
+and letters marked 'O' must be used again.",
+        word_length, guesses
+    )
+}
+
+static ASSIST_HELP_MESSAGE: &str = r"Type each guess you made and the score you got, e.g. CRANE _X__O.
+
+An 'X' means the letter was in the right spot.
+An 'O' means the letter is in the word, but somewhere else.
+An '_' means the letter isn't in the word.
+
+After each guess, the answers which are still possible are listed,
+along with the guesses expected to narrow them down the most.
+Type 'quit' to stop.";
+
+#[derive(Clone, Debug)]
+enum Turn {
+    PlayValidWord(Word),
+    PlayInvalidWord(WordParseError),
+    DisplayHelpMessage,
+    DisplaySecretWord,
+    GiveUp,
+    NewGame,
+    Quit,
+    NoOp,
+}
+
+impl Turn {
+    fn parse(input: &str, dictionary: &Dictionary) -> Turn {
+        let input = input.trim();
+
+        match input.to_lowercase().as_str() {
+            "" => return Turn::NoOp,
+            "help" => return Turn::DisplayHelpMessage,
+            "?" => return Turn::DisplaySecretWord,
+            "give up" => return Turn::GiveUp,
+            "new" | "new game" => return Turn::NewGame,
+            "quit" | "exit" => return Turn::Quit,
+            _ => {}
+        }
+
+        match dictionary.parse_guess(input) {
+            Ok(word) => Turn::PlayValidWord(word),
+            Err(err) => Turn::PlayInvalidWord(err),
+        }
+    }
+}
+
+fn advance_game(turn: Turn, style: TerminalStyle, game: Game) -> Game {
+    match turn {
+        // Typical case
+        Turn::PlayValidWord(word) => match game.try_with_prediction(word) {
+            Ok(new_game) => {
+                print_player_knowledge(&new_game, style);
+                new_game
+            }
+            Err(msg) => {
+                println!("Invalid guess: {}", msg);
+                game
+            }
+        },
+
+        // Cases with no state change
+        Turn::PlayInvalidWord(msg) => {
+            println!("Invalid word: {}", msg);
+            game
+        }
+        Turn::DisplayHelpMessage => {
+            println!(
+                "{}",
+                help_message(game.word_length(), game.maximum_guesses())
+            );
+            game
+        }
+        Turn::DisplaySecretWord => {
+            // offset to line up with prompt
+            match game.opponent() {
+                Opponent::Secret(secret_word) => println!("    {}", secret_word),
+                Opponent::Adversary(candidates) => {
+                    println!(
+                        "    One of {} words, e.g. {}",
+                        candidates.len(),
+                        candidates[0]
+                    )
+                }
+            }
+            game
+        }
+        // Ending the game is up to the loop playing it
+        Turn::GiveUp | Turn::NewGame | Turn::Quit | Turn::NoOp => game,
+    }
+}
+
+/// A turn in assistant mode, where guesses are scored by a game played elsewhere.
+#[derive(Clone, Debug)]
+enum AssistTurn {
+    RecordPlay(Play),
+    RecordInvalidPlay(String),
+    DisplayHelpMessage,
+    Quit,
+    NoOp,
+}
+
+impl AssistTurn {
+    fn parse(input: &str, word_length: usize) -> AssistTurn {
+        let input = input.trim();
+
+        match input.to_lowercase().as_str() {
+            "" => return AssistTurn::NoOp,
+            "help" => return AssistTurn::DisplayHelpMessage,
+            "quit" | "exit" => return AssistTurn::Quit,
+            _ => {}
+        }
+
+        let parts: Vec<&str> = input.split_whitespace().collect();
+        let [prediction, score] = parts[..] else {
+            let msg = "Type a guess and its score, e.g. CRANE _X__O.";
+            return AssistTurn::RecordInvalidPlay(msg.to_string());
+        };
+
+        match (prediction.parse::<Word>(), score.parse::<WordScore>()) {
+            (Ok(prediction), _) if prediction.length() != word_length => {
+                let err = WordParseError::WrongLength {
+                    expected: word_length,
+                };
+                AssistTurn::RecordInvalidPlay(format!("Invalid word: {}", err))
+            }
+            (Ok(prediction), Ok(score)) if score.0.len() != word_length => {
+                let msg = format!("Invalid score: Score must be as long as {}.", prediction);
+                AssistTurn::RecordInvalidPlay(msg)
+            }
+            (Ok(prediction), Ok(score)) => AssistTurn::RecordPlay(Play { prediction, score }),
+            (Err(err), _) => AssistTurn::RecordInvalidPlay(format!("Invalid word: {}", err)),
+            (_, Err(err)) => AssistTurn::RecordInvalidPlay(format!("Invalid score: {}", err)),
+        }
+    }
+}
+
+/// Read, evaluate, print, loop, for a game played elsewhere, until it ends or input ends.
+fn assist_loop(solver: &Solver, config: &GameConfig) {
+    let mut plays: Vec<Play> = Vec::new();
+
+    loop {
+        let remaining_guesses = config
+            .maximum_guesses
+            .map(|maximum_guesses| maximum_guesses.saturating_sub(plays.len()));
+
+        if plays.last().is_some_and(|play| play.score.is_winner()) {
+            println!("Solved!");
+            return;
+        } else if remaining_guesses == Some(0) {
+            println!("Out of guesses.");
+            return;
+        }
+
+        print_remaining_guesses(remaining_guesses);
+
+        let Some(input) = read_line() else {
+            println!();
+            return;
+        };
+        match AssistTurn::parse(&input, solver.word_length()) {
+            AssistTurn::RecordPlay(play) => {
+                plays.push(play);
+                print_suggestions(solver, &plays, config.difficulty);
+            }
+            AssistTurn::RecordInvalidPlay(msg) => println!("{}", msg),
+            AssistTurn::DisplayHelpMessage => println!("{}", ASSIST_HELP_MESSAGE),
+            AssistTurn::Quit => return,
+            AssistTurn::NoOp => {}
+        }
+    }
+}
+
+/// Reads a command from each line of input and writes a response line for each, until input ends.
+/// The game isn't saved and doesn't count towards the player's statistics.
+fn json_loop(config: GameConfig) {
+    let mut session = protocol::Session::new(config);
+    let mut stdout = io::stdout().lock();
+
+    let mut response =
+        serde_json::to_string(&session.state()).expect("Failed to serialize response.");
+    let mut lines = io::stdin().lines();
+
+    loop {
+        writeln!(stdout, "{}", response).expect("Failed to write to stdout.");
+        stdout.flush().expect("Failed to flush stdout.");
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("Failed to read from stdin: {}", err);
+                return;
+            }
+            None => return,
+        };
+        response = session.handle_line(&line);
+    }
+}
+
+/// Plays a single-board game in the full-screen terminal UI if `tui` is set,
+/// or else a session of games line by line in the console.
+fn play(
+    config: &GameConfig,
+    save_file: Option<&SaveFile>,
+    palette: Palette,
+    style: TerminalStyle,
+    tui: bool,
+    game: Game,
+) {
+    if tui {
+        #[cfg(feature = "tui")]
+        return tui_game(config, save_file, palette, style, game);
+    }
+    session_loop(config, save_file, palette, style, ConsoleGame::Single(game));
+}
+
+/// Plays a game in the full-screen terminal UI, then prints how it ended like [session_loop].
+/// The game is saved after every play; saving stops after the first failure.
+/// Quitting early leaves the game saved, to be resumed next time.
+#[cfg(feature = "tui")]
+fn tui_game(
+    config: &GameConfig,
+    save_file: Option<&SaveFile>,
+    palette: Palette,
+    style: TerminalStyle,
+    game: Game,
+) {
+    let mut save_file = save_file;
+    let played = tui::play(&config.dictionary, style, game, |game| {
+        let err = save_file?.update(config, game).err()?;
+        save_file = None;
+        Some(err.to_string())
+    });
+
+    match played {
+        Ok(game) if game.calculate_status() == GameStatus::Active => {
+            if save_file.is_some() && !game.plays().is_empty() {
+                println!("Your game is saved. Run wordle --tui to carry on.");
+            }
+        }
+        Ok(game) => {
+            print_plays(&game, style);
+            print_game_ending(&game, palette);
+        }
+        Err(err) => {
+            eprintln!("Failed to draw the terminal UI: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// A game played line by line in the console, on one board or several.
+enum ConsoleGame {
+    Single(Game),
+    Multi(MultiGame),
+}
+
+impl ConsoleGame {
+    fn calculate_status(&self) -> GameStatus {
+        match self {
+            ConsoleGame::Single(game) => game.calculate_status(),
+            ConsoleGame::Multi(game) => game.calculate_status(),
+        }
+    }
+
+    fn remaining_guesses(&self) -> Option<usize> {
+        match self {
+            ConsoleGame::Single(game) => game.remaining_guesses(),
+            ConsoleGame::Multi(game) => game.remaining_guesses(),
+        }
+    }
+
+    fn play_count(&self) -> usize {
+        match self {
+            ConsoleGame::Single(game) => game.plays().len(),
+            ConsoleGame::Multi(game) => game.predictions.len(),
+        }
+    }
+
+    /// Starts a game like this one, with as many boards, under the given rules.
+    fn next_game(&self, config: &GameConfig) -> ConsoleGame {
+        match self {
+            ConsoleGame::Single(_) => ConsoleGame::Single(Game::new(config)),
+            ConsoleGame::Multi(game) => {
+                let game = MultiGame::from_config(config, game.boards.len())
+                    .expect("The next game has a random answer.");
+                ConsoleGame::Multi(game)
+            }
+        }
+    }
+}
+
+/// Where a session of console games is at.
+enum SessionState {
+    /// A game is being played.
+    Playing(ConsoleGame),
+    /// The last game has ended, and the player is asked whether to play again.
+    GameOver(ConsoleGame),
+    /// The game after the last one is about to start.
+    NextGame(ConsoleGame),
+    /// The player quit, or input ended.
+    Quit,
+}
+
+/// How the games of a session went.
+#[derive(Clone, Copy, Debug, Default)]
+struct Tally {
+    won: usize,
+    lost: usize,
+    given_up: usize,
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "This session: Played: {} / Won: {} / Lost: {} / Given up: {}",
+            self.won + self.lost + self.given_up,
+            self.won,
+            self.lost,
+            self.given_up
+        )
+    }
+}
+
+/// Read, evaluate, print, loop, through as many games as the player wants,
+/// on one board or several.
+/// Single-board games are saved after every play; saving stops after the first failure.
+/// Once a game ends, its result is printed for sharing, in the given palette,
+/// followed by how the games of the session went so far.
+/// Later games have the same rules, and a random word unless the first one was absurd.
+fn session_loop(
+    config: &GameConfig,
+    save_file: Option<&SaveFile>,
+    palette: Palette,
+    style: TerminalStyle,
+    game: ConsoleGame,
+) {
+    let mut config = config.clone();
+    let mut save_file = save_file;
+    let mut tally = Tally::default();
+    let mut state = SessionState::Playing(game);
+
+    loop {
+        state = match state {
+            SessionState::Playing(game) => match game.calculate_status() {
+                status @ (GameStatus::Won | GameStatus::Lost) => {
+                    print_ending(&game, palette);
+                    if status == GameStatus::Won {
+                        tally.won += 1;
+                    } else {
+                        tally.lost += 1;
+                    }
+                    println!("{}", tally);
+                    SessionState::GameOver(game)
+                }
+                GameStatus::Active => {
+                    print_remaining_guesses(game.remaining_guesses());
+                    let turn = match read_line() {
+                        Some(input) => Turn::parse(&input, &config.dictionary),
+                        None => {
+                            println!();
+                            Turn::Quit
+                        }
+                    };
+
+                    match turn {
+                        Turn::GiveUp => {
+                            give_up(&game, &mut save_file);
+                            tally.given_up += 1;
+                            println!("{}", tally);
+                            SessionState::GameOver(game)
+                        }
+                        Turn::NewGame => {
+                            give_up(&game, &mut save_file);
+                            tally.given_up += 1;
+                            SessionState::NextGame(game)
+                        }
+                        Turn::Quit => {
+                            if save_file.is_some() && game.play_count() > 0 {
+                                println!("Your game is saved. Run wordle to carry on.");
+                            }
+                            SessionState::Quit
+                        }
+                        turn => match game {
+                            ConsoleGame::Single(game) => {
+                                let turn_count = game.plays().len();
+                                let new_game: Game = advance_game(turn, style, game);
+
+                                if new_game.plays().len() > turn_count {
+                                    if let Some(Err(err)) = save_file
+                                        .map(|save_file| save_file.update(&config, &new_game))
+                                    {
+                                        eprintln!("{}", err);
+                                        save_file = None;
+                                    }
+                                }
+                                SessionState::Playing(ConsoleGame::Single(new_game))
+                            }
+                            ConsoleGame::Multi(game) => SessionState::Playing(ConsoleGame::Multi(
+                                advance_multi_game(turn, style, game),
+                            )),
+                        },
+                    }
+                }
+            },
+            SessionState::GameOver(game) => {
+                print!("Play again? (y/n) ");
+                io::stdout().flush().expect("Failed to flush stdout.");
+
+                match read_line()
+                    .map(|input| input.trim().to_lowercase())
+                    .as_deref()
+                {
+                    Some("y" | "yes" | "play again" | "new" | "new game") => {
+                        SessionState::NextGame(game)
+                    }
+                    Some("n" | "no" | "quit" | "exit") => SessionState::Quit,
+                    Some(_) => SessionState::GameOver(game),
+                    None => {
+                        println!();
+                        SessionState::Quit
+                    }
+                }
+            }
+            SessionState::NextGame(game) => {
+                config = next_config(&config);
+                println!("New game!");
+                SessionState::Playing(game.next_game(&config))
+            }
+            SessionState::Quit => return,
+        };
+    }
+}
+
+/// The rules of the game to play after this one, with a new word.
+/// The daily puzzle, a chosen word and a seeded game would otherwise be played again.
+fn next_config(config: &GameConfig) -> GameConfig {
+    match config.answer {
+        AnswerSelection::Adversarial => config.clone(),
+        _ => config.with_answer(AnswerSelection::Random),
+    }
+}
+
+/// Ends a game before it is over, revealing the word and deleting the save,
+/// so the game isn't resumed next time. A single-board game given up is added to the
+/// statistics as a loss, like running out of guesses.
+fn give_up(game: &ConsoleGame, save_file: &mut Option<&SaveFile>) {
+    let game = match game {
+        ConsoleGame::Single(game) => game.give_up(),
+        ConsoleGame::Multi(game) => {
+            println!("The words were: {}", secret_words(game));
+            return;
+        }
+    };
+
+    match game.opponent() {
+        Opponent::Secret(secret_word) => println!("The word was: {}", secret_word),
+        Opponent::Adversary(candidates) => {
+            println!(
+                "The word was one of {} words, e.g. {}",
+                candidates.len(),
+                candidates[0]
+            )
+        }
+    }
+
+    if let Some(Err(err)) = save_file.map(|save_file| save_file.delete()) {
+        eprintln!("{}", err);
+        *save_file = None;
+    }
+    record_statistics(&game);
+}
+
+/// Prints how a finished game ended. A single-board game is followed by
+/// its result for sharing and the player's statistics, which don't cover multi-board games.
+fn print_ending(game: &ConsoleGame, palette: Palette) {
+    match game {
+        ConsoleGame::Single(game) => print_game_ending(game, palette),
+        ConsoleGame::Multi(game) => match game.calculate_status() {
+            GameStatus::Won => println!("You solved all {} boards!", game.boards.len()),
+            GameStatus::Lost => println!("You lost :(\nThe words were: {}", secret_words(game)),
+            GameStatus::Active => {}
+        },
+    }
+}
+
+/// Prints how a finished game ended, its result for sharing, and the player's statistics.
+fn print_game_ending(game: &Game, palette: Palette) {
+    match game.calculate_status() {
+        GameStatus::Won => println!("You're a winner, baby!"),
+        GameStatus::Lost => println!("You lost :(\nThe word was: {}", game.secret_word()),
+        GameStatus::Active => return,
+    }
+    print_share_text(game, palette);
+    print_replay_hint(game);
+    record_statistics(game);
+}
+
+fn advance_multi_game(turn: Turn, style: TerminalStyle, game: MultiGame) -> MultiGame {
+    match turn {
+        Turn::PlayValidWord(word) => match game.try_with_prediction(word) {
+            Ok(new_game) => {
+                print_boards(&new_game, style);
+                new_game
+            }
+            Err(msg) => {
+                println!("Invalid guess: {}", msg);
+                game
+            }
+        },
+        Turn::PlayInvalidWord(msg) => {
+            println!("Invalid word: {}", msg);
+            game
+        }
+        Turn::DisplayHelpMessage => {
+            let word_length = game.boards[0].secret_word.length();
+            println!("{}", help_message(word_length, game.maximum_guesses));
+            game
+        }
+        Turn::DisplaySecretWord => {
+            println!("    {}", secret_words(&game)); // offset to line up with prompt
+            game
+        }
+        // Ending the game is up to the loop playing it
+        Turn::GiveUp | Turn::NewGame | Turn::Quit | Turn::NoOp => game,
+    }
+}
+
+// Views
+
+/// Reads a line from the console into an owned [String],
+/// or returns `None` once input has ended or can't be read.
+fn read_line() -> Option<String> {
+    let mut input_buffer = String::new();
+    match io::stdin().read_line(&mut input_buffer) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input_buffer),
+    }
+}
+
+/// Prints the number of remaining guesses as a prompt, or "(∞)" if they are unlimited.
+fn print_remaining_guesses(remaining_guesses: Option<usize>) {
+    match remaining_guesses {
+        Some(remaining_guesses) => print!("({}) ", remaining_guesses),
+        None => print!("(∞) "),
+    }
+    io::stdout().flush().expect("Failed to flush stdout.");
+}
+
+/// Describes a number of guesses for a sentence, e.g. "9 guesses" or "unlimited guesses".
+fn describe_guesses(maximum_guesses: Option<usize>) -> String {
+    match maximum_guesses {
+        Some(maximum_guesses) => format!("{} guesses", maximum_guesses),
+        None => "unlimited guesses".to_string(),
+    }
+}
+
+/// Prints the answers which are still possible after the plays, and the best next guesses.
+fn print_suggestions(solver: &Solver, plays: &[Play], difficulty: Difficulty) {
+    // Only list a few candidates, there are thousands before the first guess
+    const MAXIMUM_LISTED: usize = 10;
+
+    let candidates = solver.candidates_after(plays);
+    match candidates.len() {
+        0 => {
+            println!("    No answers match these scores.");
+            return;
+        }
+        1 => {
+            println!("    The answer is {}.", candidates[0]);
+            return;
+        }
+        count => {
+            print!("    {} candidates: ", count);
+            let listed: Vec<String> = candidates
+                .iter()
+                .take(MAXIMUM_LISTED)
+                .map(|word| word.to_string())
+                .collect();
+            print!("{}", listed.join(", "));
+            if count > MAXIMUM_LISTED {
+                print!(", ...");
+            }
+            println!();
+        }
+    }
+
+    let best: Vec<String> = solver
+        .suggestions_after(plays, difficulty)
+        .iter()
+        .take(5)
+        .map(|suggestion| suggestion.to_string())
+        .collect();
+    println!("    Best guesses: {}", best.join(", "));
+}
+
+/// Prints the score of the last play on each board, leaving out boards solved earlier,
+/// and the letters ruled out on every board still to solve.
+fn print_boards(game: &MultiGame, style: TerminalStyle) {
+    let turn = game.predictions.len();
+    // Plays are shown one column per letter in every style
+    let word_length = game.boards[0].secret_word.length();
+
+    print!("   "); // offset to line up with prompt
+    for board in &game.boards {
+        match board.plays.get(turn - 1) {
+            Some(play) => print!(" {}", style.play(play)),
+            None => print!(" {:width$}", "", width = word_length),
+        }
+    }
+
+    let bad: String = game.letter_knowledge().bad.iter().collect();
+    println!(
+        " // solved: {}/{} / bad: {}",
+        game.solved_count(),
+        game.boards.len(),
+        bad
+    );
+}
+
+/// The secret words of every board, separated by spaces.
+fn secret_words(game: &MultiGame) -> String {
+    let words: Vec<String> = game
+        .boards
+        .iter()
+        .map(|board| board.secret_word.to_string())
+        .collect();
+    words.join(" ")
+}
+
+/// Adds a finished game to the player's statistics, and prints a summary of them.
+fn record_statistics(game: &Game) {
+    let Some(statistics_file) = StatisticsFile::default_location() else {
+        return;
+    };
+
+    match statistics_file.record(game) {
+        Ok(statistics) => println!(
+            "Played: {} / Win %: {:.0} / Current streak: {}. Run \"wordle stats\" for more.",
+            statistics.games_played(),
+            statistics.win_percentage(),
+            statistics.current_streak()
+        ),
+        Err(err) => eprintln!("{}", err),
+    }
+}
+
+/// Prints each play of a game so far, e.g. when it is resumed.
+fn print_plays(game: &Game, style: TerminalStyle) {
+    for play in game.plays() {
+        match style {
+            // offset to line up with prompt
+            TerminalStyle::Plain => println!("    {} {}", play.prediction, play.score),
+            TerminalStyle::Colored(_) => println!("    {}", style.play(play)),
+        }
+    }
+}
+
+/// Prints the result of a finished game, to copy and share without spoiling the word.
+fn print_share_text(game: &Game, palette: Palette) {
+    if let Some(share_text) = wordle::share_text(game, palette) {
+        println!("\n{}\n", share_text);
+    }
+}
+
+/// Prints how to play the same game again, if it was chosen by a seed.
+fn print_replay_hint(game: &Game) {
+    if let Puzzle::Seeded(seed) = game.puzzle {
+        println!("Replay this game with --seed {}", seed);
+    }
+}
+
+/// Prints the score for the last play, the known letter positions,
+/// and the player's knowledge of "good" and "bad" letters.
+fn print_player_knowledge(game: &Game, style: TerminalStyle) {
+    let last_play: &Play = game.plays().last().unwrap();
+    let letter_knowledge: LetterKnowledge = game.letter_knowledge();
+
+    // offset to line up with prompt
+    println!(
+        "    {} // {}",
+        style.play(last_play),
+        style.letter_knowledge(&game.constraints().pattern(), &letter_knowledge)
+    );
+}
@@ -0,0 +1,359 @@
+//! A JSON-lines protocol for playing from other programs, e.g. bots and test scripts:
+//! each line read is a [Command], and each line written is a [Response].
+
+use serde::{Deserialize, Serialize};
+
+use wordle::{
+    AnswerSelection, DailyPuzzle, Difficulty, Game, GameConfig, GameStatus, LetterKnowledge,
+    PlayError, Word, WordParseError, WordScore,
+};
+
+/// A line of input.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Command {
+    /// Plays a guess, e.g. `{"guess": "CRANE"}`.
+    Guess(String),
+    /// Starts a new game, e.g. `{"new_game": {"word_length": 6, "hard": true}}`.
+    NewGame(NewGame),
+}
+
+/// The rules of a new game, like the command line flags of the same names.
+/// Every field is optional, e.g. `{"new_game": {}}` starts a random game with the default rules,
+/// except that the word length stays that of the last game.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NewGame {
+    pub word_length: Option<usize>,
+    /// Number of guesses, [GameConfig::DEFAULT_MAXIMUM_GUESSES] if not given.
+    pub maximum_guesses: Option<usize>,
+    pub unlimited_guesses: bool,
+    pub hard: bool,
+    pub daily: bool,
+    pub seed: Option<u64>,
+    pub absurd: bool,
+}
+
+/// A line of output.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    /// The game after a command.
+    Game(GameState),
+    /// Why a command failed. The game is left as it was.
+    Error(ErrorResponse),
+}
+
+/// Everything a player can see of the game.
+#[derive(Clone, Debug, Serialize)]
+pub struct GameState {
+    pub status: GameStatus,
+    pub word_length: usize,
+    /// The last guess played, if any.
+    pub guess: Option<Word>,
+    /// The score of the last guess, e.g. "_XX_O".
+    pub score: Option<WordScore>,
+    /// `None` if guesses are unlimited.
+    pub remaining_guesses: Option<usize>,
+    /// Correctly placed letters, with "_" for the others, e.g. "_RA__".
+    pub pattern: String,
+    pub letters: LetterKnowledge,
+    /// The secret word, revealed once the game has ended.
+    pub answer: Option<Word>,
+}
+
+impl GameState {
+    pub fn new(game: &Game) -> Self {
+        let status = game.calculate_status();
+        let last_play = game.plays().last();
+
+        Self {
+            status,
+            word_length: game.word_length(),
+            guess: last_play.map(|play| play.prediction.clone()),
+            score: last_play.map(|play| play.score.clone()),
+            remaining_guesses: game.remaining_guesses(),
+            pattern: game.constraints().pattern(),
+            letters: game.letter_knowledge(),
+            answer: match status {
+                GameStatus::Active => None,
+                GameStatus::Won | GameStatus::Lost => Some(game.secret_word().clone()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    /// The error for people, which may change between versions unlike the code.
+    pub message: String,
+}
+
+/// Reasons a command can fail, serialized as stable "snake_case" codes to match on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The line isn't valid JSON, or isn't a known command.
+    InvalidCommand,
+    /// A new game's rules can't be played, e.g. there is no word list for its word length.
+    InvalidRules,
+    /// See [WordParseError::InvalidLength].
+    InvalidLength,
+    /// See [WordParseError::WrongLength] and [PlayError::WrongLength].
+    WrongLength,
+    /// See [WordParseError::InvalidCharacters].
+    InvalidCharacters,
+    /// See [WordParseError::NotInWordList].
+    NotInWordList,
+    /// See [PlayError::AlreadyWon].
+    AlreadyWon,
+    /// See [PlayError::GameOver].
+    GameOver,
+    /// See [PlayError::HardModeViolation].
+    HardModeViolation,
+}
+
+impl From<&WordParseError> for ErrorCode {
+    fn from(err: &WordParseError) -> Self {
+        match err {
+            WordParseError::InvalidLength => Self::InvalidLength,
+            WordParseError::WrongLength { .. } => Self::WrongLength,
+            WordParseError::InvalidCharacters => Self::InvalidCharacters,
+            WordParseError::NotInWordList => Self::NotInWordList,
+        }
+    }
+}
+
+impl From<&PlayError> for ErrorCode {
+    fn from(err: &PlayError) -> Self {
+        match err {
+            PlayError::AlreadyWon => Self::AlreadyWon,
+            PlayError::GameOver => Self::GameOver,
+            PlayError::WrongLength { .. } => Self::WrongLength,
+            PlayError::HardModeViolation(_) => Self::HardModeViolation,
+        }
+    }
+}
+
+impl Response {
+    fn error(code: ErrorCode, message: impl ToString) -> Self {
+        Self::Error(ErrorResponse {
+            code,
+            message: message.to_string(),
+        })
+    }
+}
+
+/// The game being played over the protocol, replaced by every [Command::NewGame].
+#[derive(Clone, Debug)]
+pub struct Session {
+    config: GameConfig,
+    game: Game,
+}
+
+impl Session {
+    /// Starts a game with the config, until a new game is chosen.
+    pub fn new(config: GameConfig) -> Self {
+        let game = Game::new(&config);
+        Self { config, game }
+    }
+
+    /// The game as it is, e.g. to show before the first command.
+    pub fn state(&self) -> Response {
+        Response::Game(GameState::new(&self.game))
+    }
+
+    pub fn handle(&mut self, command: Command) -> Response {
+        match command {
+            Command::Guess(guess) => {
+                let word = match self.config.dictionary.parse_guess(&guess) {
+                    Ok(word) => word,
+                    Err(err) => return Response::error(ErrorCode::from(&err), err),
+                };
+                match self.game.try_with_prediction(word) {
+                    Ok(game) => self.game = game,
+                    Err(err) => return Response::error(ErrorCode::from(&err), err),
+                }
+            }
+            Command::NewGame(new_game) => match self.config_for(&new_game) {
+                Ok(config) => {
+                    self.game = Game::new(&config);
+                    self.config = config;
+                }
+                Err(msg) => return Response::error(ErrorCode::InvalidRules, msg),
+            },
+        }
+
+        self.state()
+    }
+
+    /// Handles a line of JSON, and returns the response as a line of JSON without the newline.
+    pub fn handle_line(&mut self, line: &str) -> String {
+        let response = match serde_json::from_str(line) {
+            Ok(command) => self.handle(command),
+            Err(err) => Response::error(ErrorCode::InvalidCommand, err),
+        };
+        serde_json::to_string(&response).expect("Failed to serialize response.")
+    }
+
+    /// The config for a new game, or why its rules can't be played.
+    fn config_for(&self, new_game: &NewGame) -> Result<GameConfig, String> {
+        let config = match new_game.word_length {
+            Some(word_length) => self
+                .config
+                .with_word_length(word_length)
+                .ok_or_else(|| format!("No word list for {}-letter words.", word_length))?,
+            None => self.config.clone(),
+        };
+
+        let maximum_guesses = match (new_game.unlimited_guesses, new_game.maximum_guesses) {
+            (true, _) => None,
+            (false, Some(0)) => return Err("Games need at least one guess.".to_string()),
+            (false, maximum_guesses) => {
+                Some(maximum_guesses.unwrap_or(GameConfig::DEFAULT_MAXIMUM_GUESSES))
+            }
+        };
+        let difficulty = if new_game.hard {
+            Difficulty::Hard
+        } else {
+            Difficulty::Normal
+        };
+        let answer = if new_game.absurd {
+            AnswerSelection::Adversarial
+        } else if new_game.daily {
+            AnswerSelection::Daily(DailyPuzzle::today())
+        } else if let Some(seed) = new_game.seed {
+            AnswerSelection::Seeded(seed)
+        } else {
+            AnswerSelection::Random
+        };
+
+        Ok(config
+            .with_maximum_guesses(maximum_guesses)
+            .with_difficulty(difficulty)
+            .with_answer(answer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use wordle::{AnswerSelection, Difficulty, GameConfig};
+
+    use crate::test_util::word;
+
+    use super::Session;
+
+    fn handle(session: &mut Session, command: Value) -> Value {
+        serde_json::from_str(&session.handle_line(&command.to_string())).unwrap()
+    }
+
+    fn session() -> Session {
+        Session::new(GameConfig::default().with_answer(AnswerSelection::Word(word("CRANE"))))
+    }
+
+    #[test]
+    fn test_guesses() {
+        let mut session = session();
+
+        assert_eq!(
+            handle(&mut session, json!({"guess": "brawn"})),
+            json!({"game": {
+                "status": "Active",
+                "word_length": 5,
+                "guess": "BRAWN",
+                "score": "_XX_O",
+                "remaining_guesses": 5,
+                "pattern": "_RA__",
+                "letters": {
+                    "good": ["A", "N", "R"],
+                    "bad": ["B", "W"],
+                    "unknown": [
+                        "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "O", "P", "Q", "S",
+                        "T", "U", "V", "X", "Y", "Z"
+                    ]
+                },
+                "answer": null
+            }})
+        );
+
+        let response = handle(&mut session, json!({"guess": "CRANE"}));
+        assert_eq!(response["game"]["status"], "Won");
+        assert_eq!(response["game"]["answer"], "CRANE");
+    }
+
+    fn error_code(session: &mut Session, command: Value) -> Value {
+        handle(session, command)["error"]["code"].clone()
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let mut session = session();
+
+        for (command, code) in [
+            (json!({"guess": "CRANES"}), "wrong_length"),
+            (json!({"guess": "CR4NE"}), "invalid_characters"),
+            (json!({"guess": "QQQQQ"}), "not_in_word_list"),
+            (json!({"guess": 42}), "invalid_command"),
+            (json!({"resign": true}), "invalid_command"),
+            (json!({"new_game": {"word_length": 99}}), "invalid_rules"),
+            (json!({"new_game": {"maximum_guesses": 0}}), "invalid_rules"),
+        ] {
+            assert_eq!(error_code(&mut session, command), code);
+        }
+        assert!(session
+            .handle_line("not json")
+            .contains(r#""code":"invalid_command""#));
+
+        handle(&mut session, json!({"guess": "CRANE"}));
+        assert_eq!(
+            error_code(&mut session, json!({"guess": "SLOTH"})),
+            "already_won"
+        );
+    }
+
+    #[test]
+    fn test_play_error_codes() {
+        let config = GameConfig::default()
+            .with_difficulty(Difficulty::Hard)
+            .with_maximum_guesses(Some(2))
+            .with_answer(AnswerSelection::Word(word("CRANE")));
+        let mut session = Session::new(config);
+
+        handle(&mut session, json!({"guess": "BRAWN"}));
+        assert_eq!(
+            error_code(&mut session, json!({"guess": "SLOTH"})),
+            "hard_mode_violation"
+        );
+
+        handle(&mut session, json!({"guess": "BRAND"}));
+        assert_eq!(
+            error_code(&mut session, json!({"guess": "CRANE"})),
+            "game_over"
+        );
+    }
+
+    #[test]
+    fn test_new_game_rules() {
+        let mut session = session();
+
+        let response = handle(
+            &mut session,
+            json!({"new_game": {"word_length": 6, "unlimited_guesses": true}}),
+        );
+        assert_eq!(response["game"]["word_length"], 6);
+        assert_eq!(response["game"]["remaining_guesses"], Value::Null);
+
+        let response = handle(&mut session, json!({"new_game": {"maximum_guesses": 2}}));
+        assert_eq!(response["game"]["word_length"], 6);
+        assert_eq!(response["game"]["remaining_guesses"], 2);
+        assert!(session.game.plays().is_empty());
+
+        assert_eq!(
+            error_code(&mut session, json!({"new_game": {"hard": "yes"}})),
+            "invalid_command"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::io::IsTerminal;
+
+use wordle::word::LetterScore;
+use wordle::{LetterKnowledge, Palette, Play};
+
+/// How scores are drawn in a terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalStyle {
+    /// The "XO_" notation, for output which isn't a terminal or where colors are unwanted.
+    Plain,
+    /// The letters of each guess on colored tiles, using ANSI escape codes.
+    Colored(Palette),
+}
+
+impl TerminalStyle {
+    /// Colors in the palette if stdout is a terminal, unless the `NO_COLOR` environment variable
+    /// is set (see <https://no-color.org>).
+    pub fn detect(palette: Palette) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::choose(palette, std::io::stdout().is_terminal(), no_color)
+    }
+
+    fn choose(palette: Palette, is_terminal: bool, no_color: bool) -> Self {
+        if is_terminal && !no_color {
+            Self::Colored(palette)
+        } else {
+            Self::Plain
+        }
+    }
+
+    /// A play, as its score in plain style or as its letters on colored tiles.
+    pub fn play(&self, play: &Play) -> String {
+        match self {
+            Self::Plain => play.score.to_string(),
+            Self::Colored(palette) => play
+                .prediction
+                .letters()
+                .zip(&play.score.0)
+                .map(|(letter, &letter_score)| {
+                    tile(*palette, &letter.to_string(), Some(letter_score))
+                })
+                .collect(),
+        }
+    }
+
+    /// What the player knows about each letter so far: in plain style, the known letter positions
+    /// and the "good", "bad" and "unknown" letters; in colored style, the alphabet with each
+    /// letter on the tile of its best score.
+    pub fn letter_knowledge(&self, pattern: &str, letter_knowledge: &LetterKnowledge) -> String {
+        match self {
+            Self::Plain => {
+                let good: String = letter_knowledge.good.iter().collect();
+                let bad: String = letter_knowledge.bad.iter().collect();
+                let unknown: String = letter_knowledge.unknown.iter().collect();
+                format!(
+                    "pattern: {} / good: {} / bad: {} / unknown: {}",
+                    pattern, good, bad, unknown
+                )
+            }
+            Self::Colored(palette) => {
+                let letters: String = LetterKnowledge::ALPHABET
+                    .iter()
+                    .map(|&letter| {
+                        let letter_score = best_score(letter, pattern, letter_knowledge);
+                        tile(*palette, &letter.to_string(), letter_score)
+                    })
+                    .collect();
+                format!("pattern: {} / letters: {}", pattern, letters)
+            }
+        }
+    }
+
+    /// A letter as a key or a tile on the board, three characters wide. In plain style,
+    /// correctly placed letters are in brackets, letters present elsewhere in parentheses,
+    /// and letters not in the word are lowercase.
+    #[cfg(feature = "tui")]
+    pub fn tile(&self, letter: char, letter_score: Option<LetterScore>) -> String {
+        match (self, letter_score) {
+            (Self::Colored(palette), _) => tile(*palette, &format!(" {} ", letter), letter_score),
+            (Self::Plain, Some(LetterScore::PlacedCorrectly)) => format!("[{}]", letter),
+            (Self::Plain, Some(LetterScore::PresentElsewhere)) => format!("({})", letter),
+            (Self::Plain, Some(LetterScore::NotPresent)) => {
+                format!(" {} ", letter.to_ascii_lowercase())
+            }
+            (Self::Plain, None) => format!(" {} ", letter),
+        }
+    }
+}
+
+/// The best score a letter has had so far, given the known letter positions,
+/// or `None` if it hasn't been played yet.
+pub(crate) fn best_score(
+    letter: char,
+    pattern: &str,
+    letter_knowledge: &LetterKnowledge,
+) -> Option<LetterScore> {
+    if pattern.contains(letter) {
+        Some(LetterScore::PlacedCorrectly)
+    } else if letter_knowledge.good.contains(&letter) {
+        Some(LetterScore::PresentElsewhere)
+    } else if letter_knowledge.bad.contains(&letter) {
+        Some(LetterScore::NotPresent)
+    } else {
+        None
+    }
+}
+
+/// Text on a tile colored for its score, or left as is if it hasn't been scored yet.
+pub(crate) fn tile(palette: Palette, text: &str, letter_score: Option<LetterScore>) -> String {
+    // Foreground and background of each tile, as ANSI SGR parameters
+    let colors = match (palette, letter_score) {
+        (_, None) => return text.to_string(),
+        (Palette::Standard, Some(LetterScore::PlacedCorrectly)) => "30;42",
+        (Palette::Standard, Some(LetterScore::PresentElsewhere)) => "30;43",
+        (Palette::HighContrast, Some(LetterScore::PlacedCorrectly)) => "30;48;5;208",
+        (Palette::HighContrast, Some(LetterScore::PresentElsewhere)) => "97;48;5;33",
+        (_, Some(LetterScore::NotPresent)) => "97;100",
+    };
+    format!("\x1b[{}m{}\x1b[0m", colors, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use wordle::{Game, LetterKnowledge, Palette};
+
+    use crate::test_util::word;
+
+    use super::TerminalStyle;
+
+    #[test]
+    fn test_colors_only_terminals() {
+        let palette = Palette::HighContrast;
+        assert_eq!(
+            TerminalStyle::choose(palette, true, false),
+            TerminalStyle::Colored(palette)
+        );
+        assert_eq!(
+            TerminalStyle::choose(palette, false, false),
+            TerminalStyle::Plain
+        );
+        assert_eq!(
+            TerminalStyle::choose(palette, true, true),
+            TerminalStyle::Plain
+        );
+    }
+
+    #[test]
+    fn test_colored_play() {
+        let game = Game::from_secret_word(word("CRANE")).with_prediction(word("BRAWN"));
+        let play = &game.plays()[0];
+
+        assert_eq!(TerminalStyle::Plain.play(play), "_XX_O");
+        assert_eq!(
+            TerminalStyle::Colored(Palette::HighContrast).play(play),
+            "\x1b[97;100mB\x1b[0m\
+             \x1b[30;48;5;208mR\x1b[0m\
+             \x1b[30;48;5;208mA\x1b[0m\
+             \x1b[97;100mW\x1b[0m\
+             \x1b[97;48;5;33mN\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_letter_knowledge() {
+        let game = Game::from_secret_word(word("CRANE")).with_prediction(word("BRAWN"));
+        let pattern = game.constraints().pattern();
+        let letter_knowledge: LetterKnowledge = game.letter_knowledge();
+
+        assert_eq!(
+            TerminalStyle::Plain.letter_knowledge(&pattern, &letter_knowledge),
+            "pattern: _RA__ / good: ANR / bad: BW / unknown: CDEFGHIJKLMOPQSTUVXYZ"
+        );
+
+        let colored =
+            TerminalStyle::Colored(Palette::Standard).letter_knowledge(&pattern, &letter_knowledge);
+        assert!(colored.starts_with("pattern: _RA__ / letters: \x1b[30;42mA\x1b[0m"));
+        assert!(colored.contains("\x1b[97;100mB\x1b[0mCDEFGHIJKLM\x1b[30;43mN\x1b[0m"));
+        assert!(colored.ends_with("V\x1b[97;100mW\x1b[0mXYZ"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Helpers shared by the unit tests.
+
+use wordle::Word;
+
+/// Parses a word, which the test knows to be valid.
+pub(crate) fn word(s: &str) -> Word {
+    s.parse().unwrap()
+}
@@ -0,0 +1,310 @@
+//! A full-screen terminal UI: the board, a keyboard colored from what the player knows,
+//! and a message line, redrawn after every keystroke.
+
+use std::io;
+use std::io::Write;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use wordle::{Dictionary, Game, GameStatus};
+
+use crate::terminal::TerminalStyle;
+
+/// Keys of the on-screen keyboard, row by row.
+const KEYBOARD: [&str; 3] = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+
+/// Lets the player type guesses until the game ends or they press Esc, and returns the game.
+///
+/// `after_play` is called with the game after every valid guess, e.g. to save it,
+/// and may return a message to show the player.
+pub fn play(
+    dictionary: &Dictionary,
+    style: TerminalStyle,
+    game: Game,
+    mut after_play: impl FnMut(&Game) -> Option<String>,
+) -> io::Result<Game> {
+    let mut screen = Screen::new(game);
+    let _raw_terminal = RawTerminal::enter()?;
+    let mut stdout = io::stdout();
+
+    loop {
+        screen.draw(&mut stdout, style)?;
+
+        let Event::Key(key) = crossterm::event::read()? else {
+            continue; // redraw, e.g. after the terminal is resized
+        };
+        match screen.handle_key(key, dictionary) {
+            Action::Continue => {}
+            Action::Played => {
+                screen.message = after_play(&screen.game);
+                if screen.game.calculate_status() != GameStatus::Active {
+                    break;
+                }
+            }
+            Action::Quit => break,
+        }
+    }
+
+    Ok(screen.game)
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped,
+/// even if drawing fails.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// What happened after a keystroke.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    /// A guess was played.
+    Played,
+    Quit,
+}
+
+/// Everything shown on screen.
+struct Screen {
+    game: Game,
+    /// The guess being typed.
+    input: String,
+    /// Why the last guess was rejected, or any other news for the player.
+    message: Option<String>,
+}
+
+impl Screen {
+    fn new(game: Game) -> Self {
+        Self {
+            game,
+            input: String::new(),
+            message: None,
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, dictionary: &Dictionary) -> Action {
+        // Some platforms also report key releases
+        if key.kind == KeyEventKind::Release {
+            return Action::Continue;
+        }
+
+        match key.code {
+            KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit
+            }
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                if self.input.len() < self.game.word_length() {
+                    self.input.push(c.to_ascii_uppercase());
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter => {
+                let played = dictionary
+                    .parse_guess(&self.input)
+                    .map_err(|err| format!("Invalid word: {}", err))
+                    .and_then(|word| {
+                        self.game
+                            .try_with_prediction(word)
+                            .map_err(|err| format!("Invalid guess: {}", err))
+                    });
+                match played {
+                    Ok(game) => {
+                        self.game = game;
+                        self.input.clear();
+                        self.message = None;
+                        return Action::Played;
+                    }
+                    Err(msg) => self.message = Some(msg),
+                }
+                return Action::Continue;
+            }
+            _ => return Action::Continue,
+        }
+
+        // Typing clears the message about the last guess
+        self.message = None;
+        Action::Continue
+    }
+
+    /// The lines on screen, top to bottom.
+    fn lines(&self, style: TerminalStyle) -> Vec<String> {
+        let word_length = self.game.word_length();
+        let pattern = self.game.constraints().pattern();
+        let letter_knowledge = self.game.letter_knowledge();
+
+        let mut lines = vec!["WORDLE".to_string(), String::new()];
+
+        for play in self.game.plays() {
+            let tiles: Vec<String> = play
+                .prediction
+                .letters()
+                .zip(&play.score.0)
+                .map(|(letter, &letter_score)| style.tile(letter, Some(letter_score)))
+                .collect();
+            lines.push(tiles.join(" "));
+        }
+
+        // The guess being typed, then empty rows for the guesses left
+        let empty_rows = match self.game.remaining_guesses() {
+            Some(remaining_guesses) => remaining_guesses.saturating_sub(1),
+            None => 0,
+        };
+        let typed: Vec<String> = (0..word_length)
+            .map(|i| match self.input.chars().nth(i) {
+                Some(letter) => format!(" {} ", letter),
+                None => " . ".to_string(),
+            })
+            .collect();
+        lines.push(typed.join(" "));
+        for _ in 0..empty_rows {
+            lines.push(vec![" . "; word_length].join(" "));
+        }
+
+        lines.push(String::new());
+        lines.push(self.message.clone().unwrap_or_default());
+        lines.push(String::new());
+
+        for (i, row) in KEYBOARD.iter().enumerate() {
+            let keys: Vec<String> = row
+                .chars()
+                .map(|letter| {
+                    let letter_score =
+                        crate::terminal::best_score(letter, &pattern, &letter_knowledge);
+                    style.tile(letter, letter_score)
+                })
+                .collect();
+            lines.push(format!("{}{}", " ".repeat(i * 2), keys.join("")));
+        }
+
+        lines.push(String::new());
+        lines.push("Type a guess and press Enter. Press Esc to quit.".to_string());
+        lines
+    }
+
+    fn draw(&self, out: &mut impl Write, style: TerminalStyle) -> io::Result<()> {
+        queue!(out, Clear(ClearType::All))?;
+        for (row, line) in self.lines(style).iter().enumerate() {
+            queue!(out, MoveTo(2, row as u16), Print(line))?;
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use wordle::{Dictionary, Game};
+
+    use crate::terminal::TerminalStyle;
+    use crate::test_util::word;
+
+    use super::{Action, Screen};
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_reader("CRANE\nBRAWN\nSLOTH\n".as_bytes()).unwrap()
+    }
+
+    fn press(screen: &mut Screen, code: KeyCode) -> Action {
+        screen.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &dictionary())
+    }
+
+    fn type_word(screen: &mut Screen, s: &str) -> Action {
+        for c in s.chars() {
+            press(screen, KeyCode::Char(c));
+        }
+        press(screen, KeyCode::Enter)
+    }
+
+    #[test]
+    fn test_typing_a_guess() {
+        let mut screen = Screen::new(Game::from_secret_word(word("CRANE")));
+
+        for c in "brawny".chars() {
+            press(&mut screen, KeyCode::Char(c));
+        }
+        assert_eq!(screen.input, "BRAWN");
+
+        press(&mut screen, KeyCode::Backspace);
+        press(&mut screen, KeyCode::Char('1'));
+        assert_eq!(screen.input, "BRAW");
+
+        assert_eq!(press(&mut screen, KeyCode::Enter), Action::Continue);
+        assert_eq!(
+            screen.message.as_deref(),
+            Some("Invalid word: Word must be five letters long.")
+        );
+
+        assert_eq!(type_word(&mut screen, "n"), Action::Played);
+        assert_eq!(screen.game.plays().len(), 1);
+        assert!(screen.input.is_empty());
+        assert!(screen.message.is_none());
+    }
+
+    #[test]
+    fn test_rejects_words_not_in_dictionary() {
+        let mut screen = Screen::new(Game::from_secret_word(word("CRANE")));
+
+        assert_eq!(type_word(&mut screen, "BUMPY"), Action::Continue);
+        assert!(screen.message.unwrap().starts_with("Invalid word: "));
+        assert!(screen.game.plays().is_empty());
+    }
+
+    #[test]
+    fn test_quitting() {
+        let mut screen = Screen::new(Game::from_secret_word(word("CRANE")));
+        assert_eq!(press(&mut screen, KeyCode::Esc), Action::Quit);
+
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(screen.handle_key(ctrl_c, &dictionary()), Action::Quit);
+    }
+
+    #[test]
+    fn test_draws_board_and_keyboard() {
+        let mut screen = Screen::new(Game::from_secret_word(word("CRANE")));
+        type_word(&mut screen, "BRAWN");
+        press(&mut screen, KeyCode::Char('c'));
+
+        let lines = screen.lines(TerminalStyle::Plain);
+        assert_eq!(
+            lines,
+            [
+                "WORDLE",
+                "",
+                " b  [R] [A]  w  (N)",
+                " C   .   .   .   . ",
+                " .   .   .   .   . ",
+                " .   .   .   .   . ",
+                " .   .   .   .   . ",
+                " .   .   .   .   . ",
+                "",
+                "",
+                "",
+                " Q  w  E [R] T  Y  U  I  O  P ",
+                "  [A] S  D  F  G  H  J  K  L ",
+                "     Z  X  C  V  b (N) M ",
+                "",
+                "Type a guess and press Enter. Press Esc to quit.",
+            ]
+        );
+    }
+}
@@ -3,7 +3,8 @@
 // #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use iced::{
-    text_input, window, Alignment, Color, Column, Element, Font, Sandbox, Settings, Text, TextInput,
+    button, text_input, window, Alignment, Button, Checkbox, Color, Column, Element, Font, Radio,
+    Row, Sandbox, Settings, Text, TextInput,
 };
 
 pub fn main() -> iced::Result {
@@ -21,9 +22,22 @@ pub fn main() -> iced::Result {
 
 #[derive(Debug, Clone)]
 struct App {
+    /// Rules for the next game, chosen before the first guess.
+    config: wordle::GameConfig,
     game: wordle::Game,
+    /// Replaces `game` when playing on several boards.
+    multi_game: Option<wordle::MultiGame>,
+    /// Where `game` is saved after every guess, unless saving has failed.
+    save_file: Option<wordle::SaveFile>,
+    /// Where `game` is recorded once it ends.
+    statistics_file: Option<wordle::StatisticsFile>,
+    /// Every game recorded so far, once `game` has ended and been recorded.
+    statistics: Option<wordle::Statistics>,
     text_input_value: String,
     text_input_state: text_input::State,
+    /// Copies the result of a finished game, in the high contrast palette if chosen.
+    share_button_state: button::State,
+    high_contrast: bool,
     flash_message: Option<String>,
 }
 
@@ -31,8 +45,25 @@ struct App {
 enum Message {
     TextInputChanged(String),
     TextInputSubmitted,
+    HardModeToggled(bool),
+    DailyToggled(bool),
+    AdversaryToggled(bool),
+    BoardsSelected(usize),
+    LengthSelected(usize),
+    GuessesSelected(Option<usize>),
+    ShareClicked,
+    HighContrastToggled(bool),
 }
 
+/// Choices of number of boards: Wordle, Dordle, Quordle and Octordle.
+const BOARD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Choices of number of guesses, `None` being unlimited.
+const GUESS_LIMITS: [Option<usize>; 4] = [Some(6), Some(8), Some(10), None];
+
+/// Boards shown side by side in the grid.
+const BOARDS_PER_ROW: usize = 4;
+
 // Fonts
 const NANUM_GOTHIC_BOLD: Font = Font::External {
     name: "NanumGothicCoding-Bold",
@@ -48,13 +79,38 @@ impl Sandbox for App {
     type Message = Message;
 
     fn new() -> Self {
-        let game = wordle::Game::new(wordle::random_word());
-        println!("Secret word is {}", game.secret_word);
+        // Resume the game saved by an earlier session, if it is still in progress
+        let save_file = wordle::SaveFile::default_location();
+        let (saved, flash_message) = match save_file.as_ref().map(wordle::SaveFile::load) {
+            Some(Ok(Some(saved)))
+                if saved.game.calculate_status() == wordle::GameStatus::Active =>
+            {
+                (Some(saved), None)
+            }
+            Some(Err(err)) => (None, Some(format!("{} Starting a new game.", err))),
+            _ => (None, None),
+        };
+        let (config, game) = match saved {
+            Some(wordle::SavedGame { config, game }) => (config, game),
+            None => {
+                let config = wordle::GameConfig::default();
+                let game = wordle::Game::new(&config);
+                (config, game)
+            }
+        };
+
         Self {
+            config,
             game,
+            multi_game: None,
+            save_file,
+            statistics_file: wordle::StatisticsFile::default_location(),
+            statistics: None,
             text_input_value: String::new(),
             text_input_state: text_input::State::focused(), // focus text input when app just opened
-            flash_message: None,
+            share_button_state: button::State::new(),
+            high_contrast: false,
+            flash_message,
         }
     }
 
@@ -69,31 +125,142 @@ impl Sandbox for App {
                     .to_uppercase()
                     .chars()
                     .filter(|c| c.is_ascii_alphabetic())
-                    .take(5)
+                    .take(self.config.word_length())
                     .collect::<String>();
             }
-            Message::TextInputSubmitted => match self.text_input_value.parse::<wordle::Word>() {
-                Ok(word) => {
-                    self.game = self.game.with_prediction(word);
-                    self.text_input_value.clear();
-                    self.flash_message = None;
+            Message::TextInputSubmitted => {
+                let guess = self.config.dictionary.parse_guess(&self.text_input_value);
+                if let Some(multi_game) = &self.multi_game {
+                    match guess.map_err(|err| err.to_string()).and_then(|word| {
+                        multi_game
+                            .try_with_prediction(word)
+                            .map_err(|err| err.to_string())
+                    }) {
+                        Ok(multi_game) => {
+                            self.multi_game = Some(multi_game);
+                            self.text_input_value.clear();
+                            self.flash_message = None;
+                        }
+                        Err(msg) => {
+                            self.flash_message = Some(msg);
+                        }
+                    }
+                    return;
+                }
+
+                match guess {
+                    Ok(word) => match self.game.try_with_prediction(word) {
+                        Ok(game) => {
+                            self.game = game;
+                            self.text_input_value.clear();
+                            self.flash_message = None;
+                            self.save_game();
+                            self.record_statistics();
+                        }
+                        Err(err) => {
+                            self.flash_message = Some(err.to_string());
+                        }
+                    },
+                    Err(err) => {
+                        self.flash_message = Some(err.to_string());
+                    }
+                }
+            }
+            Message::HardModeToggled(is_hard) => {
+                if self.can_change_settings() {
+                    let difficulty = if is_hard {
+                        wordle::Difficulty::Hard
+                    } else {
+                        wordle::Difficulty::Normal
+                    };
+                    self.config = self.config.with_difficulty(difficulty);
+                    self.game = self.game.with_difficulty(difficulty);
+                }
+            }
+            Message::DailyToggled(is_daily) => {
+                if self.can_change_settings() {
+                    let answer = if is_daily {
+                        wordle::AnswerSelection::Daily(wordle::DailyPuzzle::today())
+                    } else {
+                        wordle::AnswerSelection::Random
+                    };
+                    self.config = self.config.with_answer(answer);
+                    self.game = wordle::Game::new(&self.config);
+                }
+            }
+            Message::AdversaryToggled(is_adversarial) => {
+                if self.can_change_settings() {
+                    let answer = if is_adversarial {
+                        wordle::AnswerSelection::Adversarial
+                    } else {
+                        wordle::AnswerSelection::Random
+                    };
+                    self.config = self.config.with_answer(answer);
+                    self.game = wordle::Game::new(&self.config);
+                }
+            }
+            Message::BoardsSelected(boards) => {
+                if self.can_change_settings() {
+                    self.multi_game = None;
+                    if boards > 1 {
+                        self.start_multi_game(boards);
+                    }
+                }
+            }
+            Message::LengthSelected(word_length) => {
+                if self.can_change_settings() {
+                    if let Some(config) = self.config.with_word_length(word_length) {
+                        self.config = config;
+                        self.text_input_value.clear();
+                        self.restart();
+                    }
                 }
-                Err(err) => {
-                    self.flash_message = Some(err.to_string());
+            }
+            Message::GuessesSelected(maximum_guesses) => {
+                if self.can_change_settings() {
+                    self.config = self.config.with_maximum_guesses(maximum_guesses);
+                    self.game = self.game.with_maximum_guesses(maximum_guesses);
+                    if let Some(multi_game) = &self.multi_game {
+                        self.start_multi_game(multi_game.boards.len());
+                    }
                 }
-            },
+            }
+            Message::ShareClicked => self.copy_share_text(),
+            Message::HighContrastToggled(high_contrast) => {
+                self.high_contrast = high_contrast;
+            }
         }
     }
 
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self) -> Element<'_, Message> {
         // Layout
         let mut column = basic_column();
 
         // Title
-        let title_label = "Wordle";
+        let title_label = match self.game.puzzle {
+            wordle::Puzzle::Daily(puzzle) => format!("Wordle #{}", puzzle.number),
+            _ => "Wordle".to_string(),
+        };
         let title = Text::new(title_label).size(50).font(NANUM_GOTHIC_BOLD);
         column = column.push(title);
 
+        // Several boards
+        if let Some(multi_game) = &self.multi_game {
+            let main_content = match multi_game.calculate_status() {
+                wordle::GameStatus::Active => self.view_multi_active(),
+                status => self.view_multi_over(status),
+            };
+            return column.push(main_content).into();
+        }
+
+        // Seed, so that the game can be replayed or shared
+        if let wordle::Puzzle::Seeded(seed) = self.game.puzzle {
+            let seed_label = Text::new(format!("Seed: {}", seed))
+                .size(16)
+                .font(NANUM_GOTHIC_REGULAR);
+            column = column.push(seed_label);
+        }
+
         // Main content
         let main_content = match self.game.calculate_status() {
             wordle::GameStatus::Active => self.view_active(),
@@ -114,37 +281,239 @@ fn basic_column() -> Column<'static, Message> {
     column
 }
 
+/// The number of guesses left, or "unlimited".
+fn describe_remaining(remaining_guesses: Option<usize>) -> String {
+    match remaining_guesses {
+        Some(remaining_guesses) => remaining_guesses.to_string(),
+        None => "unlimited".to_string(),
+    }
+}
+
+fn guess_input<'a>(state: &'a mut text_input::State, value: &str) -> Element<'a, Message> {
+    let placeholder = "Enter your guess";
+    TextInput::new(state, placeholder, value, Message::TextInputChanged)
+        .on_submit(Message::TextInputSubmitted)
+        .padding(10)
+        .font(NANUM_GOTHIC_REGULAR)
+        .into()
+}
+
+fn flash_text(flash_message: &str) -> Element<'static, Message> {
+    let orangered = Color::from_rgb8(255, 69, 0);
+    Text::new(flash_message)
+        .size(20)
+        .color(orangered)
+        .font(NANUM_GOTHIC_REGULAR)
+        .into()
+}
+
+/// A labelled row of radio buttons, one per (value, label) choice.
+fn choice_row<V, I>(
+    label: &str,
+    choices: I,
+    selected: V,
+    on_select: fn(V) -> Message,
+) -> Row<'static, Message>
+where
+    V: Eq + Copy + 'static,
+    I: IntoIterator<Item = (V, String)>,
+{
+    let label = Text::new(label).size(20).font(NANUM_GOTHIC_REGULAR);
+    let mut row = Row::new().spacing(20).push(label);
+
+    for (value, choice_label) in choices {
+        let radio = Radio::new(value, choice_label, Some(selected), on_select).text_size(20);
+        row = row.push(radio);
+    }
+
+    row
+}
+
+fn boards_row(selected: usize) -> Row<'static, Message> {
+    let choices = BOARD_COUNTS.map(|boards| (boards, boards.to_string()));
+    choice_row("Boards:", choices, selected, Message::BoardsSelected)
+}
+
+fn length_row(selected: usize) -> Row<'static, Message> {
+    let choices = wordle::Dictionary::builtin_lengths().map(|length| (length, length.to_string()));
+    choice_row("Letters:", choices, selected, Message::LengthSelected)
+}
+
+fn guesses_row(selected: Option<usize>) -> Row<'static, Message> {
+    let choices = GUESS_LIMITS.map(|maximum_guesses| match maximum_guesses {
+        Some(guesses) => (maximum_guesses, guesses.to_string()),
+        None => (maximum_guesses, "Unlimited".to_string()),
+    });
+    choice_row("Guesses:", choices, selected, Message::GuessesSelected)
+}
+
+/// Games played, wins and streaks, with a bar chart of the number of guesses of each win.
+fn statistics_column(statistics: &wordle::Statistics) -> Column<'static, Message> {
+    let mut column = Column::new().spacing(4).align_items(Alignment::Start);
+
+    let lines = [
+        format!("Played: {}", statistics.games_played()),
+        format!("Win %: {:.0}", statistics.win_percentage()),
+        format!("Current streak: {}", statistics.current_streak()),
+        format!("Max streak: {}", statistics.max_streak()),
+        "Guess distribution:".to_string(),
+    ];
+    for line in lines {
+        column = column.push(Text::new(line).size(20).font(NANUM_GOTHIC_REGULAR));
+    }
+
+    for bar in statistics.distribution_bars(wordle::Statistics::BAR_WIDTH) {
+        let line = format!("{:>2} {} {}", bar.guesses, "#".repeat(bar.length), bar.wins);
+        column = column.push(Text::new(line).size(16).font(NANUM_GOTHIC_REGULAR));
+    }
+
+    column
+}
+
+/// The boards of a multi-board game in rows, each listing its plays until solved.
+fn boards_grid(multi_game: &wordle::MultiGame) -> Column<'static, Message> {
+    let mut grid = Column::new().spacing(20);
+
+    for boards in multi_game.boards.chunks(BOARDS_PER_ROW) {
+        let mut row = Row::new().spacing(20);
+
+        for board in boards {
+            let mut board_column = Column::new().spacing(4);
+            for play in &board.plays {
+                let play_label = Text::new(format!("{} {}", play.prediction, play.score))
+                    .size(16)
+                    .font(NANUM_GOTHIC_REGULAR);
+                board_column = board_column.push(play_label);
+            }
+            row = row.push(board_column);
+        }
+
+        grid = grid.push(row);
+    }
+
+    grid
+}
+
 impl App {
-    fn view_active(&mut self) -> Element<Message> {
+    /// Returns true until the first guess. Changing most settings starts a new game,
+    /// so they are fixed once a game is under way.
+    fn can_change_settings(&self) -> bool {
+        match &self.multi_game {
+            Some(multi_game) => multi_game.predictions.is_empty(),
+            None => self.game.plays().is_empty(),
+        }
+    }
+
+    /// Saves the single-board game after a guess, or deletes the save once it has ended.
+    /// Saving stops after the first failure, which is shown to the player.
+    fn save_game(&mut self) {
+        if let Some(save_file) = &self.save_file {
+            if let Err(err) = save_file.update(&self.config, &self.game) {
+                self.flash_message = Some(err.to_string());
+                self.save_file = None;
+            }
+        }
+    }
+
+    /// Adds the single-board game to the player's statistics once it has ended.
+    fn record_statistics(&mut self) {
+        if self.game.calculate_status() == wordle::GameStatus::Active {
+            return;
+        }
+
+        if let Some(statistics_file) = &self.statistics_file {
+            match statistics_file.record(&self.game) {
+                Ok(statistics) => self.statistics = Some(statistics),
+                Err(err) => self.flash_message = Some(err.to_string()),
+            }
+        }
+    }
+
+    /// Copies the result of the finished single-board game to the clipboard, to paste elsewhere.
+    fn copy_share_text(&mut self) {
+        let palette = if self.high_contrast {
+            wordle::Palette::HighContrast
+        } else {
+            wordle::Palette::Standard
+        };
+        let Some(share_text) = wordle::share_text(&self.game, palette) else {
+            return;
+        };
+
+        let copied =
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(share_text));
+        self.flash_message = Some(match copied {
+            Ok(()) => "Copied to clipboard.".to_string(),
+            Err(err) => format!("Failed to copy: {}", err),
+        });
+    }
+
+    /// Starts a new game of the same kind, e.g. after switching dictionaries.
+    fn restart(&mut self) {
+        if let Some(multi_game) = &self.multi_game {
+            self.start_multi_game(multi_game.boards.len());
+        }
+
+        self.game = wordle::Game::new(&self.config);
+    }
+
+    /// Starts a game on several boards with the config's rules, or stays on one board
+    /// with a message if the config's answer can't be chosen for each board.
+    fn start_multi_game(&mut self, boards: usize) {
+        match wordle::MultiGame::from_config(&self.config, boards) {
+            Ok(multi_game) => self.multi_game = Some(multi_game),
+            Err(err) => {
+                self.multi_game = None;
+                self.flash_message = Some(err.to_string());
+            }
+        }
+    }
+
+    fn view_active(&mut self) -> Element<'_, Message> {
         let mut column = basic_column();
 
         // Text input
-        let placeholder = "Enter your guess";
-        let text_input: Element<Message> = TextInput::new(
+        column = column.push(guess_input(
             &mut self.text_input_state,
-            placeholder,
             &self.text_input_value,
-            Message::TextInputChanged,
-        )
-        .on_submit(Message::TextInputSubmitted)
-        .padding(10)
-        .font(NANUM_GOTHIC_REGULAR)
-        .into();
-        column = column.push(text_input);
+        ));
+
+        // Difficulty, puzzle and boards
+        if self.game.plays().is_empty() {
+            column = column.push(length_row(self.config.word_length()));
+            column = column.push(guesses_row(self.game.maximum_guesses()));
+            column = column.push(boards_row(1));
+
+            let is_hard = self.game.difficulty == wordle::Difficulty::Hard;
+            let hard_mode_checkbox = Checkbox::new(is_hard, "Hard mode", Message::HardModeToggled)
+                .text_size(20)
+                .font(NANUM_GOTHIC_REGULAR);
+            column = column.push(hard_mode_checkbox);
+
+            let is_daily = matches!(self.game.puzzle, wordle::Puzzle::Daily(_));
+            let daily_checkbox = Checkbox::new(is_daily, "Daily puzzle", Message::DailyToggled)
+                .text_size(20)
+                .font(NANUM_GOTHIC_REGULAR);
+            column = column.push(daily_checkbox);
+
+            let is_adversarial = matches!(self.game.opponent(), wordle::Opponent::Adversary(_));
+            let adversary_checkbox = Checkbox::new(
+                is_adversarial,
+                "Absurd mode (the word dodges your guesses)",
+                Message::AdversaryToggled,
+            )
+            .text_size(20)
+            .font(NANUM_GOTHIC_REGULAR);
+            column = column.push(adversary_checkbox);
+        }
 
         // Flash
         if let Some(flash_message) = &self.flash_message {
-            let orangered = Color::from_rgb8(255, 69, 0);
-            let flash_text: Element<Message> = Text::new(flash_message)
-                .size(20)
-                .color(orangered)
-                .font(NANUM_GOTHIC_REGULAR)
-                .into();
-            column = column.push(flash_text);
+            column = column.push(flash_text(flash_message));
         }
 
         // Guessed words
-        for play in &self.game.plays {
+        for play in self.game.plays() {
             let wordle::Play { prediction, score } = play;
 
             let prediction_label = Text::new(prediction.to_string())
@@ -158,34 +527,181 @@ impl App {
             column = column.push(score_label);
         }
 
+        // Player knowledge
+        if !self.game.plays().is_empty() {
+            let knowledge = self.game.letter_knowledge();
+            let lines = [
+                format!("Pattern: {}", self.game.constraints().pattern()),
+                format!("In word: {}", knowledge.good.iter().collect::<String>()),
+                format!("Not in word: {}", knowledge.bad.iter().collect::<String>()),
+                format!(
+                    "Guesses left: {}",
+                    describe_remaining(self.game.remaining_guesses())
+                ),
+            ];
+
+            for line in lines {
+                let knowledge_label = Text::new(line).size(20).font(NANUM_GOTHIC_REGULAR);
+                column = column.push(knowledge_label);
+            }
+        }
+
         column.into()
     }
 
-    fn view_won(&mut self) -> Element<Message> {
+    fn view_won(&mut self) -> Element<'_, Message> {
         let mut column = basic_column();
 
         column = column.push(Text::new("You won").font(NANUM_GOTHIC_REGULAR).size(40));
 
         column = column.push(
-            Text::new(format!("The word was {}.", self.game.secret_word))
+            Text::new(format!("The word was {}.", self.game.secret_word()))
                 .font(NANUM_GOTHIC_REGULAR)
                 .size(30),
         );
 
+        column = self.push_statistics(column);
+        column = self.push_share(column);
+
         column.into()
     }
 
-    fn view_lost(&mut self) -> Element<Message> {
+    fn view_lost(&mut self) -> Element<'_, Message> {
         let mut column = basic_column();
 
         column = column.push(Text::new("You lost").font(NANUM_GOTHIC_REGULAR).size(40));
 
         column = column.push(
-            Text::new(format!("The word was {}.", self.game.secret_word))
+            Text::new(format!("The word was {}.", self.game.secret_word()))
+                .font(NANUM_GOTHIC_REGULAR)
+                .size(20),
+        );
+
+        column = self.push_statistics(column);
+        column = self.push_share(column);
+
+        column.into()
+    }
+
+    /// Adds a button below a finished game to copy its result, and the choice of palette.
+    fn push_share<'a>(&'a mut self, column: Column<'a, Message>) -> Column<'a, Message> {
+        let high_contrast_checkbox = Checkbox::new(
+            self.high_contrast,
+            "High contrast",
+            Message::HighContrastToggled,
+        )
+        .text_size(20)
+        .font(NANUM_GOTHIC_REGULAR);
+        let share_button = Button::new(
+            &mut self.share_button_state,
+            Text::new("Share").font(NANUM_GOTHIC_BOLD),
+        )
+        .on_press(Message::ShareClicked);
+
+        column.push(
+            Row::new()
+                .spacing(20)
+                .align_items(Alignment::Center)
+                .push(share_button)
+                .push(high_contrast_checkbox),
+        )
+    }
+
+    /// Adds the player's statistics below a finished game, or why they couldn't be recorded.
+    fn push_statistics<'a>(&self, column: Column<'a, Message>) -> Column<'a, Message> {
+        let mut column = column;
+
+        if let Some(flash_message) = &self.flash_message {
+            column = column.push(flash_text(flash_message));
+        }
+        if let Some(statistics) = &self.statistics {
+            column = column.push(statistics_column(statistics));
+        }
+
+        column
+    }
+
+    fn view_multi_active(&mut self) -> Element<'_, Message> {
+        let mut column = basic_column();
+        let multi_game = match &self.multi_game {
+            Some(multi_game) => multi_game,
+            None => return column.into(),
+        };
+
+        // Text input
+        column = column.push(guess_input(
+            &mut self.text_input_state,
+            &self.text_input_value,
+        ));
+
+        // Boards
+        if multi_game.predictions.is_empty() {
+            column = column.push(length_row(self.config.word_length()));
+            column = column.push(boards_row(multi_game.boards.len()));
+        }
+
+        // Flash
+        if let Some(flash_message) = &self.flash_message {
+            column = column.push(flash_text(flash_message));
+        }
+
+        column = column.push(boards_grid(multi_game));
+
+        // Player knowledge, combined for the boards still to solve
+        if !multi_game.predictions.is_empty() {
+            let knowledge = multi_game.letter_knowledge();
+            let lines = [
+                format!(
+                    "Solved: {}/{}",
+                    multi_game.solved_count(),
+                    multi_game.boards.len()
+                ),
+                format!(
+                    "Guesses left: {}",
+                    describe_remaining(multi_game.remaining_guesses())
+                ),
+                format!(
+                    "Not in any word: {}",
+                    knowledge.bad.iter().collect::<String>()
+                ),
+            ];
+
+            for line in lines {
+                let knowledge_label = Text::new(line).size(20).font(NANUM_GOTHIC_REGULAR);
+                column = column.push(knowledge_label);
+            }
+        }
+
+        column.into()
+    }
+
+    fn view_multi_over(&mut self, status: wordle::GameStatus) -> Element<'_, Message> {
+        let mut column = basic_column();
+        let multi_game = match &self.multi_game {
+            Some(multi_game) => multi_game,
+            None => return column.into(),
+        };
+
+        let result_label = if status == wordle::GameStatus::Won {
+            "You won"
+        } else {
+            "You lost"
+        };
+        column = column.push(Text::new(result_label).font(NANUM_GOTHIC_REGULAR).size(40));
+
+        let secret_words: Vec<String> = multi_game
+            .boards
+            .iter()
+            .map(|board| board.secret_word.to_string())
+            .collect();
+        column = column.push(
+            Text::new(format!("The words were {}.", secret_words.join(", ")))
                 .font(NANUM_GOTHIC_REGULAR)
                 .size(20),
         );
 
+        column = column.push(boards_grid(multi_game));
+
         column.into()
     }
 }
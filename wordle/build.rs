@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Word lengths supported by the game, see `Word::MIN_LENGTH` and `Word::MAX_LENGTH`.
+const WORD_LENGTHS: std::ops::RangeInclusive<usize> = 4..=11;
+
+/// Word lists compiled into the crate, as (constant name, file name in "words/<length>/").
+/// The answers are required for each length, the other allowed guesses are optional.
+/// A word may appear only once across all lists.
+const WORD_LISTS: [(&str, &str, bool); 2] = [
+    ("ANSWERS", "answers.txt", true),
+    ("ALLOWED_GUESSES", "allowed.txt", false),
+];
+
+fn main() {
+    // Validate the plain text word lists and compile them into "$OUT_DIR/words.rs".
+    // Each list becomes one string of concatenated uppercase words per word length,
+    // so that invalid entries fail the build instead of panicking at runtime.
+    println!("cargo:rerun-if-changed=words");
+
+    let mut lengths: Vec<usize> = Vec::new();
+    for entry in fs::read_dir("words").expect("Failed to read words/") {
+        let entry = entry.unwrap();
+        println!("cargo:rerun-if-changed={}", entry.path().display());
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        match name.parse::<usize>() {
+            Ok(length) if WORD_LENGTHS.contains(&length) => lengths.push(length),
+            _ => panic!(
+                "words/{}: word lists must be in a directory named after their word length, \
+                 from {} to {}.",
+                name,
+                WORD_LENGTHS.start(),
+                WORD_LENGTHS.end()
+            ),
+        }
+    }
+    lengths.sort_unstable();
+
+    let mut generated = String::new();
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for (name, file_name, required) in WORD_LISTS {
+        let mut lists = String::new();
+
+        for &length in &lengths {
+            let path = format!("words/{}/{}", length, file_name);
+            println!("cargo:rerun-if-changed={}", path);
+
+            let contents = match fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(_) if !required && !Path::new(&path).exists() => continue,
+                Err(err) => panic!("Failed to read {}: {}", path, err),
+            };
+
+            let mut letters = String::new();
+            let mut count = 0;
+
+            for (index, line) in contents.lines().enumerate() {
+                let location = format!("{}:{}", path, index + 1);
+                let word = line.trim();
+
+                // Skip blank lines
+                if word.is_empty() {
+                    continue;
+                }
+
+                if word.len() != length || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+                    panic!(
+                        "{}: {:?} is not a {}-letter English word.",
+                        location, word, length
+                    );
+                }
+
+                let word = word.to_uppercase();
+                if let Some(first) = seen.insert(word.clone(), location.clone()) {
+                    panic!("{}: {} is a duplicate of {}.", location, word, first);
+                }
+
+                letters.push_str(&word);
+                count += 1;
+            }
+
+            if required && count == 0 {
+                panic!("{}: there must be at least one answer.", path);
+            }
+
+            lists.push_str(&format!(
+                "    // {} words from \"{}\"\n    ({}, \"{}\"),\n",
+                count, path, length, letters
+            ));
+        }
+
+        generated.push_str(&format!(
+            "/// Words by length, concatenated.\nconst {}: &[(usize, &str)] = &[\n{}];\n\n",
+            name, lists
+        ));
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("words.rs"), generated).unwrap();
+}
@@ -0,0 +1,170 @@
+use crate::dictionary::{DailyPuzzle, Dictionary};
+use crate::game::Difficulty;
+use crate::word::Word;
+
+/// How the secret word of a new game is chosen. See [crate::Puzzle] for how it was chosen.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnswerSelection {
+    /// A random answer from the dictionary, chosen by a random seed.
+    #[default]
+    Random,
+    /// The answer for a seed, see [Dictionary::seeded_answer].
+    Seeded(u64),
+    /// The answer of the day, see [Dictionary::daily_answer].
+    Daily(DailyPuzzle),
+    /// No word is chosen up front, see [crate::Opponent::Adversary].
+    Adversarial,
+    /// A given word, which need not be in the dictionary.
+    Word(Word),
+}
+
+/// The rules a game is played by, chosen by the player before starting it.
+///
+/// ```rust
+/// use wordle::{Game, GameConfig};
+///
+/// let config = GameConfig::default()
+///     .with_word_length(6)
+///     .unwrap()
+///     .with_maximum_guesses(Some(7));
+/// let game = Game::new(&config);
+///
+/// assert_eq!(game.word_length(), 6);
+/// assert_eq!(game.remaining_guesses(), Some(7));
+/// ```
+///
+/// With the "serde" feature, the dictionary is saved with the config, see [Dictionary].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+    /// The answers and allowed guesses, which also set the word length.
+    pub dictionary: Dictionary,
+    /// Guesses allowed before the game is lost, or `None` for unlimited guesses.
+    pub maximum_guesses: Option<usize>,
+    pub difficulty: Difficulty,
+    pub answer: AnswerSelection,
+}
+
+impl GameConfig {
+    /// Guesses allowed in the original game.
+    pub const DEFAULT_MAXIMUM_GUESSES: usize = 6;
+
+    pub fn with_dictionary(&self, dictionary: Dictionary) -> Self {
+        let mut config = self.clone();
+        config.dictionary = dictionary;
+        config
+    }
+
+    /// Switches to the built-in dictionary of words with the given number of letters,
+    /// if there is one. See [Dictionary::builtin].
+    pub fn with_word_length(&self, word_length: usize) -> Option<Self> {
+        Dictionary::builtin(word_length).map(|dictionary| self.with_dictionary(dictionary))
+    }
+
+    pub fn with_maximum_guesses(&self, maximum_guesses: Option<usize>) -> Self {
+        let mut config = self.clone();
+        config.maximum_guesses = maximum_guesses;
+        config
+    }
+
+    pub fn with_difficulty(&self, difficulty: Difficulty) -> Self {
+        let mut config = self.clone();
+        config.difficulty = difficulty;
+        config
+    }
+
+    pub fn with_answer(&self, answer: AnswerSelection) -> Self {
+        let mut config = self.clone();
+        config.answer = answer;
+        config
+    }
+
+    /// Number of letters in the words of the game.
+    pub fn word_length(&self) -> usize {
+        match &self.answer {
+            AnswerSelection::Word(word) => word.length(),
+            _ => self.dictionary.word_length(),
+        }
+    }
+}
+
+/// The rules of the original game, with the built-in five-letter dictionary.
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            dictionary: Dictionary::default(),
+            maximum_guesses: Some(Self::DEFAULT_MAXIMUM_GUESSES),
+            difficulty: Difficulty::default(),
+            answer: AnswerSelection::default(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::{DailyPuzzle, Dictionary, Difficulty, Game};
+
+    use super::{AnswerSelection, GameConfig};
+
+    #[test]
+    fn test_config_round_trips_through_serde() {
+        let puzzle = DailyPuzzle::new(chrono::NaiveDate::from_ymd_opt(2022, 3, 1).unwrap());
+        let config = GameConfig::default()
+            .with_word_length(6)
+            .unwrap()
+            .with_maximum_guesses(None)
+            .with_difficulty(Difficulty::Hard)
+            .with_answer(AnswerSelection::Daily(puzzle));
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["dictionary"], serde_json::json!({"builtin": 6}));
+        assert_eq!(json["maximum_guesses"], serde_json::Value::Null);
+
+        let loaded: GameConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.word_length(), 6);
+        assert_eq!(loaded.maximum_guesses, None);
+        assert_eq!(loaded.difficulty, Difficulty::Hard);
+        assert_eq!(loaded.answer, AnswerSelection::Daily(puzzle));
+    }
+
+    #[test]
+    fn test_config_keeps_a_custom_dictionary() {
+        let answers = "CRANE\nSLOTH\n".as_bytes();
+        let allowed = "BUMPY\nAUDIO\n".as_bytes();
+        let dictionary = Dictionary::from_readers(answers, allowed).unwrap();
+        let config = GameConfig::default()
+            .with_dictionary(dictionary)
+            .with_answer(AnswerSelection::Seeded(7));
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json["dictionary"],
+            serde_json::json!({
+                "custom": {"answers": ["CRANE", "SLOTH"], "allowed": ["AUDIO", "BUMPY"]}
+            })
+        );
+
+        let loaded: GameConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.dictionary.answers(), config.dictionary.answers());
+        assert!(loaded.dictionary.parse_guess("audio").is_ok());
+        assert!(loaded.dictionary.parse_guess("ghost").is_err());
+        assert_eq!(
+            Game::new(&loaded).secret_word(),
+            Game::new(&config).secret_word()
+        );
+    }
+
+    #[test]
+    fn test_config_needs_a_builtin_dictionary() {
+        let json = serde_json::json!({
+            "dictionary": {"builtin": 11},
+            "maximum_guesses": 6,
+            "difficulty": "Normal",
+            "answer": "Random",
+        });
+
+        let err = serde_json::from_value::<GameConfig>(json).unwrap_err();
+        assert_eq!(err.to_string(), "No word list for 11-letter words.");
+    }
+}
@@ -1,39 +1,850 @@
+use chrono::{Local, NaiveDate};
 use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
 use std::str::FromStr;
 
-use crate::Word;
+use crate::{Word, WordParseError};
 
-/// Static dictionary. Every item is guaranteed to be valid as a [Word].
-const DICTIONARY: &[&str] = &[
-    "CIGAR", "REBUT", "SISSY", "HUMPH", "AWAKE", "BLUSH", "FOCAL", "EVADE", "NAVAL", "SERVE",
-    "HEATH", "DWARF", "MODEL", "KARMA", "STINK", "GRADE", "QUIET", "BENCH", "ABATE", "FEIGN",
-];
+// Defines ANSWERS and ALLOWED_GUESSES, compiled from "words/<length>/*.txt" by the build script.
+// Every word is guaranteed to be valid as a [Word], and to appear only once.
+include!(concat!(env!("OUT_DIR"), "/words.rs"));
 
-/// Chooses a random [Word] from a static dictionary.
+/// The concatenated words of the given length in a word list compiled by the build script.
+fn builtin_list(lists: &'static [(usize, &'static str)], word_length: usize) -> &'static str {
+    lists
+        .iter()
+        .find(|(length, _)| *length == word_length)
+        .map_or("", |(_, list)| list)
+}
+
+/// Iterates over a word list compiled by the build script.
+fn builtin_words(list: &'static str, word_length: usize) -> impl Iterator<Item = Word> {
+    (0..list.len())
+        .step_by(word_length)
+        .map(move |start| Word::from_validated(&list[start..start + word_length]))
+}
+
+/// The words a game is played with: the possible secret words ("answers"),
+/// and the larger set of real words accepted as guesses. Every word is the same length.
+///
+/// The built-in five-letter dictionary is the [Default], and [Dictionary::builtin] has
+/// the other lengths. Built-in lengths without a list of allowed guesses accept any guess
+/// of the right length. Custom dictionaries, such as themed word lists,
+/// can be loaded from newline-delimited text with one word per line.
+///
+/// With the "serde" feature, a built-in dictionary is saved as its word length,
+/// and a custom dictionary as its words, so that either loads as it was saved.
+///
+/// ```rust
+/// use wordle::{Dictionary, WordParseError};
+///
+/// let dictionary = Dictionary::default();
+///
+/// assert!(dictionary.parse_guess("crane").is_ok());
+/// assert!(matches!(
+///     dictionary.parse_guess("qzxjk"),
+///     Err(WordParseError::NotInWordList)
+/// ));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dictionary {
+    word_length: usize,
+    answers: Vec<Word>,
+    allowed: HashSet<Word>,
+    /// Whether guesses must be in `allowed`.
+    checks_guesses: bool,
+    /// Whether this is the built-in dictionary of its word length.
+    builtin: bool,
+}
+
+impl Dictionary {
+    /// Number of letters in the words of the [Default] dictionary.
+    pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+    /// Creates a dictionary from lists of answers and other allowed guesses.
+    /// The allowed guesses may repeat the answers, and must be as long as them.
+    pub fn new(answers: Vec<Word>, allowed: Vec<Word>) -> Result<Self, DictionaryError> {
+        let Some(word_length) = answers.first().map(Word::length) else {
+            return Err(DictionaryError::NoAnswers);
+        };
+
+        let allowed: HashSet<Word> = answers.iter().cloned().chain(allowed).collect();
+        if let Some(word) = allowed.iter().find(|word| word.length() != word_length) {
+            return Err(DictionaryError::MixedLengths {
+                word: word.clone(),
+                expected: word_length,
+            });
+        }
+
+        Ok(Self {
+            word_length,
+            answers,
+            allowed,
+            checks_guesses: true,
+            builtin: false,
+        })
+    }
+
+    /// The built-in dictionary of words with the given number of letters, if there is one.
+    ///
+    /// ```rust
+    /// use wordle::Dictionary;
+    ///
+    /// let dictionary = Dictionary::builtin(6).unwrap();
+    /// assert_eq!(dictionary.word_length(), 6);
+    /// assert!(dictionary.parse_guess("planet").is_ok());
+    ///
+    /// assert!(Dictionary::builtin(11).is_none());
+    /// ```
+    ///
+    /// Only the five-letter dictionary has a full list of allowed guesses. The others accept
+    /// any guess of the right length, rather than rejecting common words missing from a list.
+    pub fn builtin(word_length: usize) -> Option<Self> {
+        let answers: Vec<Word> =
+            builtin_words(builtin_list(ANSWERS, word_length), word_length).collect();
+        if answers.is_empty() {
+            return None;
+        }
+
+        let allowed_guesses = builtin_list(ALLOWED_GUESSES, word_length);
+        let allowed = answers
+            .iter()
+            .cloned()
+            .chain(builtin_words(allowed_guesses, word_length))
+            .collect();
+
+        Some(Self {
+            word_length,
+            answers,
+            allowed,
+            checks_guesses: !allowed_guesses.is_empty(),
+            builtin: true,
+        })
+    }
+
+    /// The word lengths which have a built-in dictionary, shortest first.
+    pub fn builtin_lengths() -> impl Iterator<Item = usize> {
+        ANSWERS.iter().map(|&(word_length, _)| word_length)
+    }
+
+    /// Loads a dictionary from a single word list; every word is both an answer and a guess.
+    ///
+    /// ```rust
+    /// use wordle::Dictionary;
+    ///
+    /// let fruits = "APPLE\nGRAPE\nLEMON\nMANGO\nPEACH\n";
+    /// let dictionary = Dictionary::from_reader(fruits.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(dictionary.answers().len(), 5);
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, DictionaryError> {
+        Self::new(read_words(reader, None)?, Vec::new())
+    }
+
+    /// Loads a dictionary from a list of answers and a list of other allowed guesses.
+    pub fn from_readers<A: BufRead, G: BufRead>(
+        answers: A,
+        allowed: G,
+    ) -> Result<Self, DictionaryError> {
+        let answers = read_words(answers, None)?;
+        let word_length = answers.first().map(Word::length);
+        Self::new(answers, read_words(allowed, word_length)?)
+    }
+
+    /// Loads a dictionary from a single word list file. See [Dictionary::from_reader].
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Loads a dictionary from an answers file and an allowed guesses file.
+    pub fn from_files<P: AsRef<Path>, Q: AsRef<Path>>(
+        answers: P,
+        allowed: Q,
+    ) -> Result<Self, DictionaryError> {
+        Self::from_readers(
+            BufReader::new(File::open(answers)?),
+            BufReader::new(File::open(allowed)?),
+        )
+    }
+
+    /// Number of letters in every word of the dictionary.
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    /// The words which can be chosen as the secret word.
+    pub fn answers(&self) -> &[Word] {
+        &self.answers
+    }
+
+    /// Every word listed as a guess, including the answers, in no particular order.
+    /// Other words may be accepted too, see [Dictionary::checks_guesses].
+    pub fn allowed(&self) -> impl Iterator<Item = &Word> {
+        self.allowed.iter()
+    }
+
+    /// Returns false if any word of the right length is accepted as a guess,
+    /// not only the listed ones.
+    pub fn checks_guesses(&self) -> bool {
+        self.checks_guesses
+    }
+
+    /// Returns true for the dictionaries of [Dictionary::builtin], rather than custom word lists.
+    pub fn is_builtin(&self) -> bool {
+        self.builtin
+    }
+
+    /// Returns true if the word is accepted as a guess. Every answer is also allowed.
+    pub fn is_allowed(&self, word: &Word) -> bool {
+        !self.checks_guesses || self.allowed.contains(word)
+    }
+
+    /// Parses a player's guess, accepting only words in the dictionary
+    /// if it [checks guesses](Dictionary::checks_guesses).
+    pub fn parse_guess(&self, s: &str) -> Result<Word, WordParseError> {
+        if s.chars().count() != self.word_length {
+            return Err(WordParseError::WrongLength {
+                expected: self.word_length,
+            });
+        }
+
+        let word = Word::from_str(s)?;
+
+        if self.is_allowed(&word) {
+            Ok(word)
+        } else {
+            Err(WordParseError::NotInWordList)
+        }
+    }
+
+    /// Chooses the answer for a daily puzzle.
+    ///
+    /// Puzzles follow a fixed shuffle of the answers, so every player gets the same word
+    /// on the same day, and no word repeats until every answer has been used.
+    /// The shuffle is a Fisher-Yates shuffle of the answers in their listed order,
+    /// driven by the SplitMix64 generator seeded with [DAILY_SEED].
+    /// It does not depend on the `rand` crate, so it stays the same across versions.
+    pub fn daily_answer(&self, puzzle: DailyPuzzle) -> Word {
+        let order = self.daily_order();
+        let index = puzzle.number.rem_euclid(order.len() as i64) as usize;
+        self.answers[order[index]].clone()
+    }
+
+    /// Chooses different answers for the boards of a daily puzzle played on several boards.
+    /// Each day takes the next `count` answers of the shuffle used by [Dictionary::daily_answer],
+    /// so a single board gets the day's answer. There are fewer answers if the dictionary
+    /// doesn't have `count` of them.
+    pub fn daily_answers(&self, puzzle: DailyPuzzle, count: usize) -> Vec<Word> {
+        let order = self.daily_order();
+        let count = count.min(order.len());
+        let day = puzzle.number.rem_euclid(order.len() as i64) as usize;
+
+        (0..count)
+            .map(|i| (day * count + i) % order.len())
+            .map(|index| self.answers[order[index]].clone())
+            .collect()
+    }
+
+    /// Indices of the answers in the order of the daily puzzles, see [Dictionary::daily_answer].
+    fn daily_order(&self) -> Vec<usize> {
+        let count = self.answers.len();
+        let mut order: Vec<usize> = (0..count).collect();
+
+        let mut rng = SplitMix64(DAILY_SEED);
+        for i in (1..count).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            order.swap(i, j);
+        }
+        order
+    }
+
+    /// Chooses a random answer.
+    pub fn random_answer(&self) -> Word {
+        self.random_answer_with(&mut rand::thread_rng())
+    }
+
+    /// Chooses a random answer using the given random number generator.
+    pub fn random_answer_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Word {
+        // There is always at least one answer
+        self.answers.choose(rng).unwrap().clone()
+    }
+
+    /// Chooses the answer for a seed. The same seed and dictionary always give the same answer,
+    /// so a game can be replayed or shared by its seed. Like [Dictionary::daily_answer],
+    /// it does not depend on the `rand` crate, whose sampling may change between versions.
+    pub fn seeded_answer(&self, seed: u64) -> Word {
+        let index = SplitMix64(seed).next_u64() % self.answers.len() as u64;
+        self.answers[index as usize].clone()
+    }
+
+    /// Chooses different answers for a seed, e.g. for the boards of a game played on several.
+    /// The first is [Dictionary::seeded_answer]. There are fewer answers if the dictionary
+    /// doesn't have `count` of them.
+    pub fn seeded_answers(&self, seed: u64, count: usize) -> Vec<Word> {
+        let mut order: Vec<usize> = (0..self.answers.len()).collect();
+        let count = count.min(order.len());
+
+        // The first `count` steps of a Fisher-Yates shuffle
+        let mut rng = SplitMix64(seed);
+        for i in 0..count {
+            let j = i + (rng.next_u64() % (order.len() - i) as u64) as usize;
+            order.swap(i, j);
+        }
+
+        order[..count]
+            .iter()
+            .map(|&index| self.answers[index].clone())
+            .collect()
+    }
+}
+
+/// The date of daily puzzle number 0.
+pub const DAILY_EPOCH: NaiveDate = match NaiveDate::from_ymd_opt(2021, 6, 19) {
+    Some(date) => date,
+    None => panic!("invalid epoch"),
+};
+
+/// Seed of the daily puzzle shuffle. Changing it changes every daily word.
+pub const DAILY_SEED: u64 = 0x5752_444C_4521; // "WRDLE!"
+
+/// Identifies the puzzle for one calendar day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailyPuzzle {
+    pub date: NaiveDate,
+    /// Days since [DAILY_EPOCH].
+    pub number: i64,
+}
+
+impl DailyPuzzle {
+    /// The puzzle for a calendar date.
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use wordle::dictionary::DailyPuzzle;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// assert_eq!(DailyPuzzle::new(date).number, 196);
+    /// ```
+    pub fn new(date: NaiveDate) -> Self {
+        let number = date.signed_duration_since(DAILY_EPOCH).num_days();
+        Self { date, number }
+    }
+
+    /// Today's puzzle, by the local calendar.
+    pub fn today() -> Self {
+        Self::new(Local::now().date_naive())
+    }
+}
+
+/// SplitMix64, a small pseudorandom number generator with a published, fixed algorithm.
+/// See <https://prng.di.unimi.it/splitmix64.c>.
+/// Unlike the generators in `rand`, its output is guaranteed never to change.
+struct SplitMix64(u64);
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Reads one word per line, skipping blank lines.
+/// Fails on the first malformed or repeated word, reporting its line number.
+/// Every word must have `word_length` letters, or as many as the first word if not given.
+fn read_words<R: BufRead>(
+    reader: R,
+    word_length: Option<usize>,
+) -> Result<Vec<Word>, DictionaryError> {
+    let mut words = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let text = line.trim();
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let invalid_word = |error| DictionaryError::InvalidWord {
+            line: line_number,
+            text: text.to_string(),
+            error,
+        };
+        let word = Word::from_str(text).map_err(invalid_word)?;
+
+        let expected = word_length.or(words.first().map(Word::length));
+        if let Some(expected) = expected.filter(|&expected| expected != word.length()) {
+            return Err(invalid_word(WordParseError::WrongLength { expected }));
+        }
+
+        if !seen.insert(word.clone()) {
+            return Err(DictionaryError::DuplicateWord {
+                line: line_number,
+                word,
+            });
+        }
+
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+/// Reasons a custom dictionary can't be loaded.
+#[derive(Debug)]
+pub enum DictionaryError {
+    /// The word list couldn't be read.
+    Io(io::Error),
+    /// A line of the word list isn't a valid [Word].
+    InvalidWord {
+        line: usize,
+        text: String,
+        error: WordParseError,
+    },
+    /// A word appears more than once in the same word list.
+    DuplicateWord { line: usize, word: Word },
+    /// A word is not as long as the first answer.
+    MixedLengths { word: Word, expected: usize },
+    /// There are no words to choose a secret word from.
+    NoAnswers,
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read word list: {}", err),
+            Self::InvalidWord { line, text, error } => {
+                write!(f, "Line {}: \"{}\" is invalid. {}", line, text, error)
+            }
+            Self::DuplicateWord { line, word } => {
+                write!(f, "Line {}: {} appears more than once.", line, word)
+            }
+            Self::MixedLengths { word, expected } => write!(
+                f,
+                "Words must all be the same length, but {} is not {} letters long.",
+                word, expected
+            ),
+            Self::NoAnswers => write!(f, "Word list must contain at least one word."),
+        }
+    }
+}
+
+impl From<io::Error> for DictionaryError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A saved [Dictionary]: the word length of a built-in one, or the words of a custom one.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SavedDictionary {
+    Builtin(usize),
+    Custom {
+        answers: Vec<Word>,
+        /// The other allowed guesses, in alphabetical order.
+        allowed: Vec<Word>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Dictionary {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let saved = if self.is_builtin() {
+            SavedDictionary::Builtin(self.word_length)
+        } else {
+            let answers: HashSet<&Word> = self.answers.iter().collect();
+            let mut allowed: Vec<Word> = self
+                .allowed
+                .iter()
+                .filter(|word| !answers.contains(word))
+                .cloned()
+                .collect();
+            allowed.sort_by_key(Word::to_string);
+
+            SavedDictionary::Custom {
+                answers: self.answers.clone(),
+                allowed,
+            }
+        };
+        saved.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Dictionary {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match SavedDictionary::deserialize(deserializer)? {
+            SavedDictionary::Builtin(word_length) => Self::builtin(word_length).ok_or_else(|| {
+                serde::de::Error::custom(format!("No word list for {}-letter words.", word_length))
+            }),
+            SavedDictionary::Custom { answers, allowed } => {
+                Self::new(answers, allowed).map_err(serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+/// The built-in dictionary of five-letter words.
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::builtin(Self::DEFAULT_WORD_LENGTH).expect("Five-letter words are built in.")
+    }
+}
+
+/// Chooses a random [Word] from the built-in answers.
 pub fn random_word() -> Word {
-    let &word = DICTIONARY.choose(&mut rand::thread_rng()).unwrap();
-    Word::from_str(word).unwrap()
+    random_word_with(&mut rand::thread_rng())
+}
+
+/// Chooses a random [Word] from the built-in answers using the given random number generator.
+pub fn random_word_with<R: Rng + ?Sized>(rng: &mut R) -> Word {
+    let word_length = Dictionary::DEFAULT_WORD_LENGTH;
+    let answers = builtin_list(ANSWERS, word_length);
+    let count = answers.len() / word_length;
+    let start = rng.gen_range(0..count) * word_length;
+    Word::from_validated(&answers[start..start + word_length])
+}
+
+/// Chooses the dictionary's answer for a calendar date. See [Dictionary::daily_answer].
+pub fn daily_word(dictionary: &Dictionary, date: NaiveDate) -> Word {
+    dictionary.daily_answer(DailyPuzzle::new(date))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
     use std::str::FromStr;
 
-    use super::DICTIONARY;
-    use crate::{random_word, Word};
+    use chrono::{Duration, NaiveDate};
+    use rand::rngs::mock::StepRng;
+    use rand::RngCore;
+
+    use super::{
+        builtin_list, builtin_words, daily_word, DailyPuzzle, DictionaryError, SplitMix64,
+        ALLOWED_GUESSES, ANSWERS, DAILY_EPOCH,
+    };
+    use crate::dictionary::random_word_with;
+    use crate::{Dictionary, Game, Word, WordParseError};
 
     #[test]
     fn test_every_word_in_dictionary_is_valid() {
-        for &word in DICTIONARY {
-            assert!(Word::from_str(word).is_ok());
+        for word_length in Dictionary::builtin_lengths() {
+            let answers = builtin_words(builtin_list(ANSWERS, word_length), word_length);
+            let allowed = builtin_words(builtin_list(ALLOWED_GUESSES, word_length), word_length);
+
+            for word in answers.chain(allowed) {
+                assert!(Word::from_str(&word.to_string()).is_ok());
+                assert_eq!(word.length(), word_length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_builtin_dictionary_is_full_size() {
+        let dictionary = Dictionary::default();
+
+        assert_eq!(dictionary.word_length(), 5);
+        assert!(dictionary.answers().len() > 2_000);
+        assert!(dictionary.allowed().count() > 12_000);
+    }
+
+    #[test]
+    fn test_builtin_dictionaries_by_length() {
+        let lengths: Vec<usize> = Dictionary::builtin_lengths().collect();
+        assert_eq!(lengths, [4, 5, 6, 7]);
+
+        for word_length in lengths {
+            let dictionary = Dictionary::builtin(word_length).unwrap();
+            assert_eq!(dictionary.word_length(), word_length);
+            assert!(dictionary.answers().len() > 100);
+            assert!(dictionary
+                .allowed()
+                .all(|word| word.length() == word_length));
+        }
+
+        assert!(Dictionary::builtin(8).is_none());
+    }
+
+    #[test]
+    fn test_only_full_guess_lists_are_checked() {
+        let dictionary = Dictionary::default();
+        assert!(dictionary.checks_guesses());
+        assert!(dictionary.parse_guess("QZXJK").is_err());
+
+        for (word_length, guess) in [(4, "DOGS"), (6, "PURPLE"), (7, "BETWEEN")] {
+            let dictionary = Dictionary::builtin(word_length).unwrap();
+            assert!(!dictionary.checks_guesses());
+            assert!(dictionary.parse_guess(guess).is_ok());
+        }
+
+        let dictionary = Dictionary::from_reader("CRANE\n".as_bytes()).unwrap();
+        assert!(dictionary.checks_guesses());
+        assert!(dictionary.parse_guess("SLOTH").is_err());
+    }
+
+    #[test]
+    fn test_answers_are_allowed_guesses() {
+        let dictionary = Dictionary::default();
+
+        for word in dictionary.answers() {
+            assert!(dictionary.is_allowed(word));
         }
     }
 
+    #[test]
+    fn test_rejects_guesses_not_in_word_list() {
+        let dictionary = Dictionary::default();
+
+        assert!(dictionary.parse_guess("speed").is_ok());
+        assert!(matches!(
+            dictionary.parse_guess("AAAAA"),
+            Err(WordParseError::NotInWordList)
+        ));
+        assert!(matches!(
+            dictionary.parse_guess("SPEEDS"),
+            Err(WordParseError::WrongLength { expected: 5 })
+        ));
+        assert!(matches!(
+            dictionary.parse_guess("SPEEDOMETERS"),
+            Err(WordParseError::WrongLength { expected: 5 })
+        ));
+    }
+
     #[test]
     fn test_random_word_does_not_panic() {
-        for _ in 0..10_000 {
-            let word = random_word();
+        for seed in 0..10_000 {
+            let word = random_word_with(&mut SplitMix64(seed));
             drop(word);
         }
     }
+
+    #[test]
+    fn test_random_answer_uses_given_rng() {
+        let dictionary =
+            Dictionary::from_readers("BYTES\nCACHE\nSTACK\n".as_bytes(), "".as_bytes()).unwrap();
+
+        // StepRng always yields zero, choosing the first answer
+        let mut rng = StepRng::new(0, 0);
+        assert_eq!(
+            dictionary.random_answer_with(&mut rng),
+            Word::from_str("BYTES").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_seeded_answer_is_reproducible() {
+        let dictionary = Dictionary::default();
+
+        for seed in [0, 1, 42, u64::MAX] {
+            assert_eq!(
+                dictionary.seeded_answer(seed),
+                dictionary.seeded_answer(seed)
+            );
+        }
+
+        let answers: HashSet<Word> = (0..100)
+            .map(|seed| dictionary.seeded_answer(seed))
+            .collect();
+        assert!(answers.len() > 1);
+    }
+
+    #[test]
+    fn test_seeded_answer_is_stable() {
+        let dictionary =
+            Dictionary::from_readers("BYTES\nCACHE\nSTACK\n".as_bytes(), "".as_bytes()).unwrap();
+
+        // Indexed by the first output of SplitMix64, modulo the number of answers
+        assert_eq!(
+            dictionary.seeded_answer(0),
+            Word::from_str("CACHE").unwrap()
+        );
+        assert_eq!(
+            dictionary.seeded_answer(1),
+            Word::from_str("STACK").unwrap()
+        );
+        assert_eq!(
+            dictionary.seeded_answer(42),
+            Word::from_str("CACHE").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_loads_custom_dictionary() {
+        let answers = "  bytes\nCACHE\n\nstack\n";
+        let allowed = "bytes\nheaps\n";
+        let dictionary = Dictionary::from_readers(answers.as_bytes(), allowed.as_bytes()).unwrap();
+
+        assert_eq!(dictionary.answers().len(), 3);
+        assert!(dictionary.parse_guess("HEAPS").is_ok());
+        assert!(dictionary.parse_guess("CRANE").is_err());
+
+        let game = Game::from_dictionary(&dictionary);
+        assert!(dictionary.answers().contains(game.secret_word()));
+    }
+
+    #[test]
+    fn test_reports_line_of_invalid_word() {
+        let words = "BYTES\nCACHE\nSTACKS\n";
+
+        match Dictionary::from_reader(words.as_bytes()) {
+            Err(DictionaryError::InvalidWord { line, text, error }) => {
+                assert_eq!(line, 3);
+                assert_eq!(text, "STACKS");
+                assert!(matches!(error, WordParseError::WrongLength { expected: 5 }));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reports_line_of_duplicate_word() {
+        let words = "BYTES\nCACHE\n\ncache\n";
+
+        let err = Dictionary::from_reader(words.as_bytes()).unwrap_err();
+        assert!(matches!(
+            err,
+            DictionaryError::DuplicateWord { line: 4, .. }
+        ));
+        assert_eq!(err.to_string(), "Line 4: CACHE appears more than once.");
+    }
+
+    #[test]
+    fn test_allowed_guesses_must_match_answers_length() {
+        let err =
+            Dictionary::from_readers("PLANET\n".as_bytes(), "BYTES\n".as_bytes()).unwrap_err();
+        assert!(matches!(
+            err,
+            DictionaryError::InvalidWord {
+                line: 1,
+                error: WordParseError::WrongLength { expected: 6 },
+                ..
+            }
+        ));
+
+        let words = vec![Word::from_str("PLANET").unwrap()];
+        let err = Dictionary::new(words, vec![Word::from_str("BYTES").unwrap()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Words must all be the same length, but BYTES is not 6 letters long."
+        );
+    }
+
+    #[test]
+    fn test_rejects_empty_dictionary() {
+        let err = Dictionary::from_reader("\n\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, DictionaryError::NoAnswers));
+    }
+
+    #[test]
+    fn test_split_mix_64_matches_reference() {
+        // First outputs of the reference implementation seeded with 1234567
+        let mut rng = SplitMix64(1234567);
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn test_daily_word_is_deterministic() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+
+        let dictionary = Dictionary::default();
+        assert_eq!(daily_word(&dictionary, date), daily_word(&dictionary, date));
+        assert_eq!(DailyPuzzle::new(DAILY_EPOCH).number, 0);
+        assert_eq!(DailyPuzzle::new(date).number, 1946);
+    }
+
+    #[test]
+    fn test_daily_words_are_stable() {
+        // Changing these changes the puzzle every player gets on these days
+        let dictionary = Dictionary::default();
+        let daily = |year, month, day| {
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            daily_word(&dictionary, date).to_string()
+        };
+        assert_eq!(daily(2021, 6, 19), "LITHE");
+        assert_eq!(daily(2022, 1, 1), "COMFY");
+        assert_eq!(daily(2026, 10, 17), "STAIN");
+
+        // The shuffle itself, apart from the built-in list
+        let dictionary =
+            Dictionary::from_reader("CRANE\nSLOTH\nBUMPY\nFIELD\nGHOST\n".as_bytes()).unwrap();
+        let words: Vec<String> = (0..5)
+            .map(|day| DailyPuzzle::new(DAILY_EPOCH + Duration::days(day)))
+            .map(|puzzle| dictionary.daily_answer(puzzle).to_string())
+            .collect();
+        assert_eq!(words, ["CRANE", "SLOTH", "GHOST", "BUMPY", "FIELD"]);
+    }
+
+    #[test]
+    fn test_answers_for_several_boards() {
+        let dictionary = Dictionary::default();
+        let puzzle = DailyPuzzle::new(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap());
+
+        let words = dictionary.daily_answers(puzzle, 4);
+        assert_eq!(words.len(), 4);
+        assert_eq!(words.iter().collect::<HashSet<_>>().len(), 4);
+        assert_eq!(words, dictionary.daily_answers(puzzle, 4));
+        assert_eq!(
+            dictionary.daily_answers(puzzle, 1),
+            [dictionary.daily_answer(puzzle)]
+        );
+
+        let words = dictionary.seeded_answers(42, 8);
+        assert_eq!(words.len(), 8);
+        assert_eq!(words.iter().collect::<HashSet<_>>().len(), 8);
+        assert_eq!(words, dictionary.seeded_answers(42, 8));
+        assert_eq!(words[0], dictionary.seeded_answer(42));
+
+        // There can't be more answers than the dictionary has
+        let dictionary = Dictionary::from_reader("CRANE\nSLOTH\n".as_bytes()).unwrap();
+        assert_eq!(dictionary.seeded_answers(42, 3).len(), 2);
+        assert_eq!(dictionary.daily_answers(puzzle, 3).len(), 2);
+    }
+
+    #[test]
+    fn test_daily_words_do_not_repeat_within_a_cycle() {
+        let dictionary = Dictionary::default();
+        let count = dictionary.answers().len();
+
+        let words: HashSet<Word> = (0..count as i64)
+            .map(|day| DailyPuzzle::new(DAILY_EPOCH + Duration::days(day)))
+            .map(|puzzle| dictionary.daily_answer(puzzle))
+            .collect();
+        assert_eq!(words.len(), count);
+
+        let next_cycle = DailyPuzzle::new(DAILY_EPOCH + Duration::days(count as i64));
+        assert_eq!(
+            dictionary.daily_answer(next_cycle),
+            dictionary.daily_answer(DailyPuzzle::new(DAILY_EPOCH))
+        );
+    }
 }
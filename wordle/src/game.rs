@@ -1,9 +1,240 @@
-use std::collections::BTreeSet;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 
-use crate::word::{Word, WordScore};
+use rand::Rng;
+
+use crate::config::{AnswerSelection, GameConfig};
+use crate::dictionary::{DailyPuzzle, Dictionary};
+use crate::word::{number_name, LetterScore, Word, WordScore};
+
+/// What the player knows about the number of times a letter appears in the secret word.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LetterCount {
+    /// The letter appears at least this many times.
+    AtLeast(usize),
+    /// The letter appears exactly this many times.
+    Exactly(usize),
+}
+
+impl LetterCount {
+    /// The fewest times the letter can appear.
+    pub fn minimum(&self) -> usize {
+        match *self {
+            Self::AtLeast(n) | Self::Exactly(n) => n,
+        }
+    }
+
+    /// Returns true if a word containing the letter `count` times is consistent with this.
+    pub fn admits(&self, count: usize) -> bool {
+        match *self {
+            Self::AtLeast(n) => count >= n,
+            Self::Exactly(n) => count == n,
+        }
+    }
+
+    /// Combines two observations of the same letter.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Exactly(n), _) | (_, Self::Exactly(n)) => Self::Exactly(n),
+            (Self::AtLeast(a), Self::AtLeast(b)) => Self::AtLeast(a.max(b)),
+        }
+    }
+}
+
+/// Represents everything the player has learned about the secret word from their plays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraints {
+    /// The letter known to be at each position, if any.
+    pub placed: Vec<Option<char>>,
+    /// Letters known not to be at each position.
+    pub excluded: Vec<BTreeSet<char>>,
+    /// The number of times each guessed letter is known to appear.
+    pub counts: BTreeMap<char, LetterCount>,
+}
+
+impl Constraints {
+    /// Creates constraints for a secret word of the given length, with nothing known yet.
+    pub fn new(word_length: usize) -> Self {
+        Self {
+            placed: vec![None; word_length],
+            excluded: vec![BTreeSet::new(); word_length],
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// The player gains knowledge from the score of each play.
+    ///
+    /// A correctly placed letter fixes that position, and any other letter is ruled out there.
+    /// Counting the marked copies of a letter gives its minimum count,
+    /// and an unmarked copy means there are no more than that.
+    pub fn update(&self, play: &Play) -> Self {
+        let mut constraints = self.clone();
+        // Marked copies of each letter, and whether any copy was unmarked
+        let mut observed: BTreeMap<char, (usize, bool)> = BTreeMap::new();
+
+        for (position, (letter, &score)) in play.prediction.letters().zip(&play.score.0).enumerate()
+        {
+            let (marked, unmarked) = observed.entry(letter).or_insert((0, false));
+
+            match score {
+                LetterScore::PlacedCorrectly => {
+                    constraints.placed[position] = Some(letter);
+                    *marked += 1;
+                }
+                LetterScore::PresentElsewhere => {
+                    constraints.excluded[position].insert(letter);
+                    *marked += 1;
+                }
+                LetterScore::NotPresent => {
+                    constraints.excluded[position].insert(letter);
+                    *unmarked = true;
+                }
+            }
+        }
+
+        // An unmarked copy means there are no more than the marked ones,
+        // whether it comes before or after them
+        let observed = observed.into_iter().map(|(letter, (marked, unmarked))| {
+            let count = if unmarked {
+                LetterCount::Exactly(marked)
+            } else {
+                LetterCount::AtLeast(marked)
+            };
+            (letter, count)
+        });
+
+        for (letter, count) in observed {
+            let merged = match constraints.counts.get(&letter) {
+                Some(&known) => known.merge(count),
+                None => count,
+            };
+            constraints.counts.insert(letter, merged);
+        }
+
+        constraints
+    }
+
+    /// Returns true if the word could still be the secret word.
+    pub fn admits(&self, word: &Word) -> bool {
+        let letters: Vec<char> = word.letters().collect();
+
+        if letters.len() != self.placed.len() {
+            return false;
+        }
+
+        let positions_match = letters.iter().enumerate().all(|(position, letter)| {
+            self.placed[position].is_none_or(|placed| placed == *letter)
+                && !self.excluded[position].contains(letter)
+        });
+
+        positions_match
+            && self.counts.iter().all(|(letter, count)| {
+                count.admits(letters.iter().filter(|&c| c == letter).count())
+            })
+    }
+
+    /// Letters known to be in the secret word.
+    pub fn present_letters(&self) -> BTreeSet<char> {
+        self.counts
+            .iter()
+            .filter(|(_, count)| count.minimum() > 0)
+            .map(|(&letter, _)| letter)
+            .collect()
+    }
+
+    /// Letters known not to be in the secret word.
+    pub fn absent_letters(&self) -> BTreeSet<char> {
+        self.counts
+            .iter()
+            .filter(|(_, &count)| count == LetterCount::Exactly(0))
+            .map(|(&letter, _)| letter)
+            .collect()
+    }
+
+    /// The known letters at each position, with '_' marking unknown positions.
+    ///
+    /// ```rust
+    /// # use wordle::{Game, Word};
+    /// let game = Game::from_secret_word("CRANE".parse().unwrap())
+    ///     .with_prediction("BRAWN".parse().unwrap());
+    ///
+    /// assert_eq!(game.constraints().pattern(), "_RA__");
+    /// ```
+    pub fn pattern(&self) -> String {
+        self.placed
+            .iter()
+            .map(|letter| letter.unwrap_or('_'))
+            .collect()
+    }
+
+    /// Checks that a prediction uses every hint revealed so far, as required in hard mode:
+    /// correctly placed letters must stay in place, and letters known to be present must be used.
+    pub fn hard_mode_violation(&self, prediction: &Word) -> Option<HardModeViolation> {
+        let letters: Vec<char> = prediction.letters().collect();
+
+        for (position, placed) in self.placed.iter().enumerate() {
+            if let Some(letter) = *placed {
+                if letters.get(position) != Some(&letter) {
+                    return Some(HardModeViolation::MissingPlacedLetter { position, letter });
+                }
+            }
+        }
+
+        for (&letter, count) in &self.counts {
+            let minimum = count.minimum();
+            if letters.iter().filter(|&&c| c == letter).count() < minimum {
+                return Some(HardModeViolation::MissingPresentLetter {
+                    letter,
+                    count: minimum,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// A hint from an earlier play which a hard mode prediction failed to use.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HardModeViolation {
+    /// A correctly placed letter was not kept at its position (counted from zero).
+    MissingPlacedLetter { position: usize, letter: char },
+    /// A letter known to be in the word was used fewer than `count` times.
+    MissingPresentLetter { letter: char, count: usize },
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::MissingPlacedLetter { position, letter } => {
+                write!(f, "{} letter must be {}.", ordinal(position + 1), letter)
+            }
+            Self::MissingPresentLetter { letter, count: 1 } => {
+                write!(f, "Guess must contain {}.", letter)
+            }
+            Self::MissingPresentLetter { letter, count } => {
+                write!(f, "Guess must contain {} {}'s.", count, letter)
+            }
+        }
+    }
+}
+
+/// Formats a number as "1st", "2nd", "3rd", etc.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
 
 /// Represents the player's knowledge of "good" and "bad" letters.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LetterKnowledge {
     pub good: BTreeSet<char>,
     pub bad: BTreeSet<char>,
@@ -11,28 +242,11 @@ pub struct LetterKnowledge {
 }
 
 impl LetterKnowledge {
-    const ALPHABET: [char; 26] = [
+    /// Every letter which can be good, bad or unknown, in order.
+    pub const ALPHABET: [char; 26] = [
         'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R',
         'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
     ];
-
-    /// The player should gain knowledge from the letters in their prediction.
-    /// Each letter could be revealed as a "good" or "bad" letter --
-    /// "good" meaning the letter is in the secret word.
-    pub fn update(&self, secret_word: &Word, prediction: &Word) -> Self {
-        let mut knowledge = self.clone();
-
-        for letter in prediction.letters() {
-            if secret_word.letters().any(|x| x == letter) {
-                knowledge.good.insert(letter);
-            } else {
-                knowledge.bad.insert(letter);
-            }
-            knowledge.unknown.remove(&letter);
-        }
-
-        knowledge
-    }
 }
 
 impl Default for LetterKnowledge {
@@ -45,7 +259,166 @@ impl Default for LetterKnowledge {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+impl LetterKnowledge {
+    /// Combines the knowledge of several boards, e.g. for one keyboard shared by all of them:
+    /// a letter is good if it's good on any board, and bad only if it's bad on every board.
+    pub fn combine<'a, I>(knowledge: I) -> Self
+    where
+        I: IntoIterator<Item = &'a LetterKnowledge>,
+    {
+        let mut knowledge = knowledge.into_iter();
+        let Some(first) = knowledge.next() else {
+            return Self::default();
+        };
+
+        let (good, bad) = knowledge.fold(
+            (first.good.clone(), first.bad.clone()),
+            |(good, bad), board| {
+                let good = good.union(&board.good).copied().collect();
+                let bad = bad.intersection(&board.bad).copied().collect();
+                (good, bad)
+            },
+        );
+        let unknown = LetterKnowledge::ALPHABET
+            .into_iter()
+            .filter(|letter| !good.contains(letter) && !bad.contains(letter))
+            .collect();
+
+        Self { good, bad, unknown }
+    }
+}
+
+/// Summarizes [Constraints] letter by letter -- "good" meaning the letter is in the secret word.
+impl From<&Constraints> for LetterKnowledge {
+    fn from(constraints: &Constraints) -> Self {
+        let good = constraints.present_letters();
+        let bad = constraints.absent_letters();
+        let unknown = LetterKnowledge::ALPHABET
+            .into_iter()
+            .filter(|letter| !good.contains(letter) && !bad.contains(letter))
+            .collect();
+
+        Self { good, bad, unknown }
+    }
+}
+
+/// How the secret word of a game was chosen.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Puzzle {
+    /// The secret word was supplied directly.
+    #[default]
+    Custom,
+    /// A random answer from a dictionary, chosen by a seed.
+    /// Starting a game with the same seed and dictionary replays it.
+    Seeded(u64),
+    /// The answer of the day, shared by every player.
+    Daily(DailyPuzzle),
+    /// No word is chosen up front, see [Opponent::Adversary].
+    Adversarial,
+}
+
+/// Scores the player's guesses.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Opponent {
+    /// Scores guesses against a word chosen at the start of the game.
+    Secret(Word),
+    /// Keeps every answer which agrees with the scores so far, and scores each guess
+    /// so that as many answers as possible remain. Never empty.
+    ///
+    /// Ties between scores go to the one with the fewest letters placed correctly,
+    /// then the fewest letters present, so the adversary only gives the win away when forced.
+    Adversary(Vec<Word>),
+}
+
+impl Opponent {
+    /// The word the player has to find.
+    /// An adversary may not have settled on one yet, so this is the first word it could still choose.
+    pub fn secret_word(&self) -> &Word {
+        match self {
+            Self::Secret(secret_word) => secret_word,
+            Self::Adversary(candidates) => &candidates[0],
+        }
+    }
+
+    /// Scores a prediction, narrowing down an adversary's candidates.
+    fn score(&mut self, prediction: &Word) -> WordScore {
+        match self {
+            Self::Secret(secret_word) => secret_word.guess(prediction),
+            Self::Adversary(candidates) => {
+                // Candidates grouped by their score, in order of first appearance
+                let mut buckets: Vec<(WordScore, Vec<Word>)> = Vec::new();
+                let mut bucket_indices: HashMap<WordScore, usize> = HashMap::new();
+
+                for candidate in candidates.drain(..) {
+                    let score = candidate.guess(prediction);
+                    let index = *bucket_indices.entry(score.clone()).or_insert_with(|| {
+                        buckets.push((score, Vec::new()));
+                        buckets.len() - 1
+                    });
+                    buckets[index].1.push(candidate);
+                }
+
+                let count = |score: &WordScore, letter_score: LetterScore| {
+                    score.0.iter().filter(|&&x| x == letter_score).count()
+                };
+                let (score, bucket) = buckets
+                    .into_iter()
+                    .min_by_key(|(score, bucket)| {
+                        (
+                            Reverse(bucket.len()),
+                            count(score, LetterScore::PlacedCorrectly),
+                            count(score, LetterScore::PresentElsewhere),
+                        )
+                    })
+                    .expect("An adversary needs at least one candidate.");
+
+                *candidates = bucket;
+                score
+            }
+        }
+    }
+}
+
+/// Rules chosen by the player when starting a game.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    /// Any revealed hints must be used in subsequent guesses.
+    Hard,
+}
+
+/// Reasons a prediction can't be played.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlayError {
+    /// The secret word has already been found.
+    AlreadyWon,
+    /// The game was lost, there are no guesses left.
+    GameOver,
+    /// The prediction is not as long as the secret word.
+    WrongLength { expected: usize },
+    /// In hard mode, the prediction ignores a hint from an earlier play.
+    HardModeViolation(HardModeViolation),
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyWon => write!(f, "The game is already won."),
+            Self::GameOver => write!(f, "The game is over, there are no guesses left."),
+            Self::WrongLength { expected } => {
+                write!(f, "Guess must be {} letters long.", number_name(*expected))
+            }
+            Self::HardModeViolation(violation) => write!(f, "{}", violation),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameStatus {
     Active,
     Lost,
@@ -53,42 +426,253 @@ pub enum GameStatus {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Play {
     pub prediction: Word,
     pub score: WordScore,
 }
 
+/// A game of Wordle, from the first guess until it is won or lost.
+///
+/// The plays can only be added through [Game::try_with_prediction] (or [Game::with_prediction]),
+/// so a game never has plays which break its rules, or any play after it has ended.
+///
+/// With the "serde" feature, a game is saved with its opponent and plays. The constraints are
+/// worked out again when it is loaded, which fails if the plays could not have happened.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Game {
-    pub secret_word: Word,
-    pub plays: Vec<Play>,
-    pub letter_knowledge: LetterKnowledge,
+    opponent: Opponent,
+    plays: Vec<Play>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    constraints: Constraints,
+    /// Guesses allowed before the game is lost, or `None` for unlimited guesses.
+    maximum_guesses: Option<usize>,
+    /// Whether the player gave up, which loses the game.
+    given_up: bool,
+    pub difficulty: Difficulty,
+    pub puzzle: Puzzle,
 }
 
 impl Game {
-    pub const MAXIMUM_GUESSES: i32 = 6;
+    /// Starts a game with the rules and answer selection of the config.
+    ///
+    /// ```rust
+    /// use wordle::{AnswerSelection, Difficulty, Game, GameConfig};
+    ///
+    /// let config = GameConfig::default()
+    ///     .with_maximum_guesses(None)
+    ///     .with_difficulty(Difficulty::Hard)
+    ///     .with_answer(AnswerSelection::Seeded(42));
+    /// let game = Game::new(&config);
+    ///
+    /// assert_eq!(game.remaining_guesses(), None);
+    /// assert_eq!(game.secret_word(), &config.dictionary.seeded_answer(42));
+    /// ```
+    pub fn new(config: &GameConfig) -> Game {
+        let dictionary = &config.dictionary;
+        let game = match &config.answer {
+            AnswerSelection::Random => Game::from_dictionary(dictionary),
+            AnswerSelection::Seeded(seed) => Game::seeded(dictionary, *seed),
+            AnswerSelection::Daily(puzzle) => Game::daily(dictionary, *puzzle),
+            AnswerSelection::Adversarial => Game::adversarial(dictionary),
+            AnswerSelection::Word(secret_word) => Game::from_secret_word(secret_word.clone()),
+        };
+
+        Game {
+            difficulty: config.difficulty,
+            maximum_guesses: config.maximum_guesses,
+            ..game
+        }
+    }
+
+    /// Starts a game with the given secret word and the default rules.
+    pub fn from_secret_word(secret_word: Word) -> Game {
+        Game::with_opponent(Opponent::Secret(secret_word))
+    }
 
-    pub fn new(secret_word: Word) -> Game {
+    fn with_opponent(opponent: Opponent) -> Game {
+        let constraints = Constraints::new(opponent.secret_word().length());
         Game {
-            secret_word,
+            opponent,
             plays: Vec::new(),
-            letter_knowledge: LetterKnowledge::default(),
+            constraints,
+            difficulty: Difficulty::default(),
+            puzzle: Puzzle::default(),
+            maximum_guesses: Some(GameConfig::DEFAULT_MAXIMUM_GUESSES),
+            given_up: false,
         }
     }
 
+    /// Starts a game against an adversary, which may choose any answer from the dictionary.
+    pub fn adversarial(dictionary: &Dictionary) -> Game {
+        Game {
+            puzzle: Puzzle::Adversarial,
+            ..Game::with_opponent(Opponent::Adversary(dictionary.answers().to_vec()))
+        }
+    }
+
+    /// Starts a game with a random answer from the dictionary.
+    pub fn from_dictionary(dictionary: &Dictionary) -> Game {
+        Game::from_rng(dictionary, &mut rand::thread_rng())
+    }
+
+    /// Starts a game with an answer chosen by a seed drawn from the given random number generator.
+    pub fn from_rng<R: Rng + ?Sized>(dictionary: &Dictionary, rng: &mut R) -> Game {
+        Game::seeded(dictionary, rng.gen())
+    }
+
+    /// Starts a game with the answer for a seed, see [Dictionary::seeded_answer].
+    pub fn seeded(dictionary: &Dictionary, seed: u64) -> Game {
+        Game {
+            puzzle: Puzzle::Seeded(seed),
+            ..Game::from_secret_word(dictionary.seeded_answer(seed))
+        }
+    }
+
+    /// Starts the game of the day, see [Dictionary::daily_answer].
+    pub fn daily(dictionary: &Dictionary, puzzle: DailyPuzzle) -> Game {
+        Game {
+            puzzle: Puzzle::Daily(puzzle),
+            ..Game::from_secret_word(dictionary.daily_answer(puzzle))
+        }
+    }
+
+    pub fn with_difficulty(&self, difficulty: Difficulty) -> Self {
+        let mut game = self.clone();
+        game.difficulty = difficulty;
+        game
+    }
+
+    /// Sets the number of guesses allowed, `None` meaning unlimited.
+    /// A game which has ended stays as it is, so that it can't be resumed.
+    pub fn with_maximum_guesses(&self, maximum_guesses: Option<usize>) -> Self {
+        let mut game = self.clone();
+        if game.calculate_status() == GameStatus::Active {
+            game.maximum_guesses = maximum_guesses;
+        }
+        game
+    }
+
+    /// Ends the game as lost, when the player gives up before it is over,
+    /// so that it counts as a loss in their statistics. A game which has ended stays as it is.
+    pub fn give_up(&self) -> Self {
+        let mut game = self.clone();
+        if game.calculate_status() == GameStatus::Active {
+            game.given_up = true;
+        }
+        game
+    }
+
+    /// Plays a prediction, unless the game has ended, the prediction is the wrong length,
+    /// or it breaks the rules of the game's [Difficulty].
+    ///
+    /// ```rust
+    /// use wordle::{Game, PlayError};
+    ///
+    /// let game = Game::from_secret_word("CRANE".parse().unwrap());
+    /// let game = game.try_with_prediction("CRANE".parse().unwrap()).unwrap();
+    ///
+    /// let err = game.try_with_prediction("SLOTH".parse().unwrap()).unwrap_err();
+    /// assert_eq!(err, PlayError::AlreadyWon);
+    /// ```
+    pub fn try_with_prediction(&self, prediction: Word) -> Result<Self, PlayError> {
+        self.check_playable(&prediction)?;
+
+        if self.difficulty == Difficulty::Hard {
+            if let Some(violation) = self.constraints.hard_mode_violation(&prediction) {
+                return Err(PlayError::HardModeViolation(violation));
+            }
+        }
+
+        Ok(self.with_prediction(prediction))
+    }
+
+    /// Plays a prediction without checking the rules of the game's [Difficulty].
+    /// Use [Game::try_with_prediction] for predictions which may not be valid, e.g. user input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the game has ended, or the prediction is not as long as the secret word.
     pub fn with_prediction(&self, prediction: Word) -> Self {
+        assert!(
+            self.calculate_status() == GameStatus::Active,
+            "Can't play {} after the game has ended.",
+            prediction
+        );
+        assert_eq!(
+            prediction.length(),
+            self.word_length(),
+            "Prediction must be as long as the secret word."
+        );
         let mut game = self.clone();
 
-        game.letter_knowledge = game.letter_knowledge.update(&game.secret_word, &prediction);
+        let score = game.opponent.score(&prediction);
+        let play = Play { prediction, score };
 
-        let score = game.secret_word.guess(&prediction);
-        game.plays.push(Play { prediction, score });
+        game.constraints = game.constraints.update(&play);
+        game.plays.push(play);
 
         game
     }
 
-    pub fn remaining_guesses(&self) -> usize {
-        Game::MAXIMUM_GUESSES as usize - self.plays.len()
+    /// Checks that the game is still active and the prediction is as long as the secret word,
+    /// which [Game::with_prediction] relies on.
+    fn check_playable(&self, prediction: &Word) -> Result<(), PlayError> {
+        match self.calculate_status() {
+            GameStatus::Active => {}
+            GameStatus::Won => return Err(PlayError::AlreadyWon),
+            GameStatus::Lost => return Err(PlayError::GameOver),
+        }
+
+        if prediction.length() != self.word_length() {
+            return Err(PlayError::WrongLength {
+                expected: self.word_length(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Scores the player's guesses, and may hold the secret word.
+    pub fn opponent(&self) -> &Opponent {
+        &self.opponent
+    }
+
+    /// Every prediction played so far with its score, in order.
+    pub fn plays(&self) -> &[Play] {
+        &self.plays
+    }
+
+    /// Everything the player has learned about the secret word from their plays.
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    /// Guesses allowed before the game is lost, or `None` for unlimited guesses.
+    pub fn maximum_guesses(&self) -> Option<usize> {
+        self.maximum_guesses
+    }
+
+    /// The word the player has to find, see [Opponent::secret_word].
+    pub fn secret_word(&self) -> &Word {
+        self.opponent.secret_word()
+    }
+
+    /// Number of letters in the secret word, which every prediction must have.
+    pub fn word_length(&self) -> usize {
+        self.constraints.placed.len()
+    }
+
+    /// The player's knowledge of "good" and "bad" letters, e.g. for coloring a keyboard.
+    pub fn letter_knowledge(&self) -> LetterKnowledge {
+        LetterKnowledge::from(&self.constraints)
+    }
+
+    /// Guesses left before the game is lost, or `None` if guesses are unlimited.
+    pub fn remaining_guesses(&self) -> Option<usize> {
+        self.maximum_guesses
+            .map(|maximum_guesses| maximum_guesses.saturating_sub(self.plays.len()))
     }
 
     pub fn last_score(&self) -> Option<&WordScore> {
@@ -96,11 +680,15 @@ impl Game {
     }
 
     pub fn calculate_status(&self) -> GameStatus {
+        if self.given_up {
+            return GameStatus::Lost;
+        }
+
         match self.last_score() {
             Some(score) => {
                 if score.is_winner() {
                     GameStatus::Won
-                } else if self.remaining_guesses() == 0 {
+                } else if self.remaining_guesses() == Some(0) {
                     GameStatus::Lost
                 } else {
                     GameStatus::Active
@@ -112,14 +700,500 @@ impl Game {
     }
 }
 
+/// The fields of a saved [Game], before its plays are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SavedGame {
+    opponent: Opponent,
+    plays: Vec<Play>,
+    maximum_guesses: Option<usize>,
+    #[serde(default)]
+    given_up: bool,
+    difficulty: Difficulty,
+    puzzle: Puzzle,
+}
+
+/// Loads a game saved with [serde::Serialize], replaying its plays against the opponent.
+/// Plays must score as they were saved, and can't follow the end of the game.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Game {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let saved = SavedGame::deserialize(deserializer)?;
+
+        if let Opponent::Adversary(candidates) = &saved.opponent {
+            let Some(first) = candidates.first() else {
+                return Err(D::Error::custom(
+                    "An adversary needs at least one candidate.",
+                ));
+            };
+            if candidates
+                .iter()
+                .any(|word| word.length() != first.length())
+            {
+                return Err(D::Error::custom(
+                    "An adversary's candidates must all be the same length.",
+                ));
+            }
+        }
+
+        let word_length = saved.opponent.secret_word().length();
+        let mut game = Game {
+            opponent: saved.opponent,
+            plays: Vec::new(),
+            constraints: Constraints::new(word_length),
+            maximum_guesses: saved.maximum_guesses,
+            given_up: false,
+            difficulty: saved.difficulty,
+            puzzle: saved.puzzle,
+        };
+
+        for (index, play) in saved.plays.into_iter().enumerate() {
+            game.check_playable(&play.prediction)
+                .map_err(|err| D::Error::custom(format!("Invalid play {}: {}", index + 1, err)))?;
+            game = game.with_prediction(play.prediction);
+
+            let replayed = &game.plays[index];
+            if replayed.score != play.score {
+                return Err(D::Error::custom(format!(
+                    "Invalid play {}: {} scores {}, not {}.",
+                    index + 1,
+                    replayed.prediction,
+                    replayed.score,
+                    play.score
+                )));
+            }
+        }
+
+        game.given_up = saved.given_up;
+        Ok(game)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{random_word, Game, GameStatus};
+    use std::collections::BTreeSet;
+
+    use crate::test_util::{game_with_predictions, word};
+    use crate::{random_word, AnswerSelection, Dictionary, Game, GameConfig, GameStatus};
+
+    use super::{
+        Constraints, Difficulty, HardModeViolation, LetterCount, Opponent, PlayError, Puzzle,
+    };
 
     #[test]
     fn test_new_game_is_active() {
-        let game = Game::new(random_word());
+        let game = Game::from_secret_word(random_word());
 
         assert!(game.calculate_status() == GameStatus::Active);
     }
+
+    fn adversarial_game(answers: &str) -> Game {
+        Game::adversarial(&Dictionary::from_reader(answers.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_adversary_keeps_largest_bucket() {
+        let game = adversarial_game("CATCH\nHATCH\nMATCH\nPATCH\nWATCH\n");
+
+        let game = game.with_prediction(word("HATCH"));
+        assert_eq!(game.last_score().unwrap().to_string(), "_XXXX");
+
+        let Opponent::Adversary(candidates) = game.opponent() else {
+            panic!("expected an adversary, got {:?}", game.opponent());
+        };
+        assert_eq!(
+            candidates,
+            &[word("CATCH"), word("MATCH"), word("PATCH"), word("WATCH")]
+        );
+    }
+
+    #[test]
+    fn test_adversary_only_commits_when_forced() {
+        let game = adversarial_game("ABBEY\nCRANE\n");
+
+        // Both scores leave one word, so the adversary avoids the win
+        let game = game.with_prediction(word("CRANE"));
+        assert_eq!(game.last_score().unwrap().to_string(), "__O_O");
+        assert_eq!(game.secret_word(), &word("ABBEY"));
+
+        let game = game.with_prediction(word("ABBEY"));
+        assert!(game.calculate_status() == GameStatus::Won);
+    }
+
+    #[test]
+    fn test_seeded_game_can_be_replayed() {
+        let dictionary = Dictionary::default();
+        let game = Game::from_dictionary(&dictionary);
+
+        let Puzzle::Seeded(seed) = game.puzzle else {
+            panic!("expected a seeded game, got {:?}", game.puzzle);
+        };
+        let replay = Game::seeded(&dictionary, seed);
+        assert_eq!(replay.secret_word(), game.secret_word());
+        assert_eq!(replay.puzzle, game.puzzle);
+    }
+
+    #[test]
+    fn test_new_constraints_admit_any_word() {
+        let constraints = Constraints::new(5);
+
+        assert!(constraints.admits(&word("CIGAR")));
+        assert!(constraints.admits(&word("QZXJK")));
+        assert_eq!(constraints.pattern(), "_____");
+    }
+
+    #[test]
+    fn test_constraints_track_positions() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BROWN"]);
+        let constraints = game.constraints();
+
+        assert_eq!(constraints.pattern(), "_R___");
+        assert!(constraints.excluded[4].contains(&'N'));
+        assert_eq!(constraints.present_letters(), BTreeSet::from(['N', 'R']));
+        assert_eq!(
+            constraints.absent_letters(),
+            BTreeSet::from(['B', 'O', 'W'])
+        );
+
+        assert!(constraints.admits(&word("CRANE")));
+        assert!(constraints.admits(&word("GRAND")));
+        assert!(!constraints.admits(&word("GRAIN"))); // N is not 5th
+        assert!(!constraints.admits(&word("TRACE"))); // N is missing
+        assert!(!constraints.admits(&word("DRONE"))); // O is absent
+        assert!(!constraints.admits(&word("NAVAL"))); // R is not 2nd
+    }
+
+    #[test]
+    fn test_constraints_track_letter_counts() {
+        // ABIDE has exactly one E, revealed by the unmarked second E
+        let game = game_with_predictions(Game::from_secret_word(word("ABIDE")), &["SPEED"]);
+        let constraints = game.constraints();
+
+        assert_eq!(constraints.counts[&'E'], LetterCount::Exactly(1));
+        assert_eq!(constraints.counts[&'D'], LetterCount::AtLeast(1));
+        assert_eq!(constraints.counts[&'S'], LetterCount::Exactly(0));
+
+        assert!(constraints.admits(&word("ABIDE")));
+        assert!(!constraints.admits(&word("EDGED"))); // too many E's
+        assert!(constraints.admits(&word("BRIDE")));
+        assert!(!constraints.admits(&word("MOPED"))); // P is absent, and D is not 5th
+    }
+
+    #[test]
+    fn test_constraints_count_letters_unmarked_before_marked() {
+        // The first two E's of EERIE are unmarked, but the last one is in place
+        let game = game_with_predictions(Game::from_secret_word(word("ABIDE")), &["EERIE"]);
+        let constraints = game.constraints();
+
+        assert_eq!(game.last_score().unwrap().to_string(), "___OX");
+        assert_eq!(constraints.counts[&'E'], LetterCount::Exactly(1));
+        assert!(constraints.admits(&word("ABIDE")));
+        assert!(!constraints.admits(&word("IXEXE"))); // too many E's
+    }
+
+    #[test]
+    fn test_constraints_accumulate_over_plays() {
+        let game =
+            game_with_predictions(Game::from_secret_word(word("EERIE")), &["GEESE", "THREE"]);
+        let constraints = game.constraints();
+
+        assert_eq!(constraints.pattern(), "_ER_E");
+        assert_eq!(constraints.counts[&'E'], LetterCount::AtLeast(3));
+        assert!(constraints.admits(&word("EERIE")));
+        assert!(!constraints.admits(&word("GEESE")));
+    }
+
+    #[test]
+    fn test_letter_knowledge_follows_constraints() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BROWN"]);
+        let knowledge = game.letter_knowledge();
+
+        assert_eq!(knowledge.good, BTreeSet::from(['N', 'R']));
+        assert_eq!(knowledge.bad, BTreeSet::from(['B', 'O', 'W']));
+        assert_eq!(knowledge.unknown.len(), 21);
+    }
+
+    #[test]
+    fn test_hard_mode_requires_placed_letters() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"])
+            .with_difficulty(Difficulty::Hard);
+
+        let err = game.try_with_prediction(word("FLASK")).unwrap_err();
+        assert_eq!(
+            err,
+            PlayError::HardModeViolation(HardModeViolation::MissingPlacedLetter {
+                position: 1,
+                letter: 'R'
+            })
+        );
+        assert_eq!(err.to_string(), "2nd letter must be R.");
+    }
+
+    #[test]
+    fn test_hard_mode_requires_present_letters() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BROWN"])
+            .with_difficulty(Difficulty::Hard);
+
+        let err = game.try_with_prediction(word("TRACE")).unwrap_err();
+        assert_eq!(err.to_string(), "Guess must contain N.");
+
+        let game = game_with_predictions(Game::from_secret_word(word("EERIE")), &["GEESE"])
+            .with_difficulty(Difficulty::Hard);
+
+        let err = game.try_with_prediction(word("VERGE")).unwrap_err();
+        assert_eq!(err.to_string(), "Guess must contain 3 E's.");
+    }
+
+    #[test]
+    fn test_hard_mode_allows_guesses_using_hints() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BROWN"])
+            .with_difficulty(Difficulty::Hard);
+
+        // Placing N at a position already ruled out is still allowed, as in the original game
+        let game = game.try_with_prediction(word("GRAIN")).unwrap();
+        assert_eq!(game.plays().len(), 2);
+        assert!(game.try_with_prediction(word("CRANE")).is_ok());
+    }
+
+    #[test]
+    fn test_normal_mode_ignores_hints() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"]);
+
+        assert!(game.try_with_prediction(word("FLASK")).is_ok());
+    }
+
+    #[test]
+    fn test_game_follows_guess_limit() {
+        let predictions = ["BRAWN"; 6];
+
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &predictions);
+        assert_eq!(game.remaining_guesses(), Some(0));
+        assert!(game.calculate_status() == GameStatus::Lost);
+
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &predictions[..2])
+            .with_maximum_guesses(Some(2));
+        assert!(game.calculate_status() == GameStatus::Lost);
+
+        // A lost game can't be resumed
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &predictions)
+            .with_maximum_guesses(None);
+        assert_eq!(game.remaining_guesses(), Some(0));
+        assert!(game.calculate_status() == GameStatus::Lost);
+    }
+
+    #[test]
+    fn test_game_rejects_plays_after_a_win() {
+        let game =
+            game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN", "CRANE"]);
+
+        let err = game.try_with_prediction(word("SLOTH")).unwrap_err();
+        assert_eq!(err, PlayError::AlreadyWon);
+        assert_eq!(err.to_string(), "The game is already won.");
+        assert_eq!(game.plays().len(), 2);
+        assert!(game.calculate_status() == GameStatus::Won);
+    }
+
+    #[test]
+    fn test_game_rejects_plays_after_a_loss() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"; 6]);
+
+        // Even the right answer is too late
+        let err = game.try_with_prediction(word("CRANE")).unwrap_err();
+        assert_eq!(err, PlayError::GameOver);
+        assert_eq!(game.remaining_guesses(), Some(0));
+
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"; 2])
+            .with_maximum_guesses(Some(2));
+        let err = game.try_with_prediction(word("CRANE")).unwrap_err();
+        assert_eq!(err, PlayError::GameOver);
+    }
+
+    #[test]
+    fn test_giving_up_loses_the_game() {
+        let game =
+            game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"]).give_up();
+        assert!(game.calculate_status() == GameStatus::Lost);
+        assert_eq!(
+            game.try_with_prediction(word("CRANE")).unwrap_err(),
+            PlayError::GameOver
+        );
+        assert_eq!(game.plays().len(), 1);
+
+        // A game which has ended can't be given up
+        let won =
+            game_with_predictions(Game::from_secret_word(word("CRANE")), &["CRANE"]).give_up();
+        assert!(won.calculate_status() == GameStatus::Won);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't play SLOTH after the game has ended.")]
+    fn test_with_prediction_panics_after_a_win() {
+        game_with_predictions(Game::from_secret_word(word("CRANE")), &["CRANE"])
+            .with_prediction(word("SLOTH"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't play CRANE after the game has ended.")]
+    fn test_with_prediction_panics_after_a_loss() {
+        game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"; 6])
+            .with_prediction(word("CRANE"));
+    }
+
+    #[test]
+    fn test_game_from_config() {
+        let dictionary = Dictionary::from_reader("PLANET\nROCKET\n".as_bytes()).unwrap();
+        let config = GameConfig::default()
+            .with_dictionary(dictionary)
+            .with_maximum_guesses(Some(3))
+            .with_difficulty(Difficulty::Hard);
+
+        let game = Game::new(&config);
+        assert_eq!(game.word_length(), 6);
+        assert_eq!(game.remaining_guesses(), Some(3));
+        assert_eq!(game.difficulty, Difficulty::Hard);
+        assert!(matches!(game.puzzle, Puzzle::Seeded(_)));
+
+        let game = Game::new(&config.with_answer(AnswerSelection::Word(word("ROCKET"))));
+        assert_eq!(game.secret_word(), &word("ROCKET"));
+        assert_eq!(game.puzzle, Puzzle::Custom);
+
+        let game = Game::new(&config.with_answer(AnswerSelection::Adversarial));
+        assert_eq!(game.puzzle, Puzzle::Adversarial);
+        assert_eq!(game.remaining_guesses(), Some(3));
+    }
+
+    #[test]
+    fn test_game_rejects_predictions_of_another_length() {
+        let game = Game::from_secret_word(word("PLANET"));
+        assert_eq!(game.word_length(), 6);
+
+        let err = game.try_with_prediction(word("CRANE")).unwrap_err();
+        assert_eq!(err, PlayError::WrongLength { expected: 6 });
+        assert_eq!(err.to_string(), "Guess must be six letters long.");
+
+        let game = game.try_with_prediction(word("PLANES")).unwrap();
+        assert_eq!(game.last_score().unwrap().to_string(), "XXXXX_");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_game_round_trips_through_serde() {
+        let game =
+            game_with_predictions(Game::from_secret_word(word("CRANE")), &["SLOTH", "BRAWN"])
+                .with_difficulty(Difficulty::Hard)
+                .with_maximum_guesses(Some(8));
+
+        let json = serde_json::to_value(&game).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "opponent": { "Secret": "CRANE" },
+                "plays": [
+                    { "prediction": "SLOTH", "score": "_____" },
+                    { "prediction": "BRAWN", "score": "_XX_O" },
+                ],
+                "maximum_guesses": 8,
+                "given_up": false,
+                "difficulty": "Hard",
+                "puzzle": "Custom",
+            })
+        );
+
+        let loaded: Game = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), json);
+        assert_eq!(loaded.constraints(), game.constraints());
+        assert_eq!(loaded.remaining_guesses(), Some(6));
+
+        let loaded = loaded.try_with_prediction(word("CRANE")).unwrap();
+        assert!(loaded.calculate_status() == GameStatus::Won);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_adversarial_and_daily_games_round_trip_through_serde() {
+        let dictionary = Dictionary::from_reader("CRANE\nSLOTH\nBUMPY\n".as_bytes()).unwrap();
+
+        let game = Game::adversarial(&dictionary).with_prediction(word("CRANE"));
+        let json = serde_json::to_string(&game).unwrap();
+        let loaded: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+        assert_eq!(loaded.plays().len(), 1);
+
+        let date = chrono::NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let puzzle = crate::DailyPuzzle::new(date);
+        let game = Game::daily(&dictionary, puzzle);
+        let loaded: Game = serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+        assert_eq!(loaded.puzzle, Puzzle::Daily(puzzle));
+        assert_eq!(loaded.secret_word(), game.secret_word());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_saved_games_must_follow_the_rules() {
+        let load = |json: serde_json::Value| {
+            serde_json::from_value::<Game>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        let saved = |plays: &[(&str, &str)], maximum_guesses: usize| {
+            let plays: Vec<_> = plays
+                .iter()
+                .map(|(prediction, score)| {
+                    serde_json::json!({ "prediction": prediction, "score": score })
+                })
+                .collect();
+            serde_json::json!({
+                "opponent": { "Secret": "CRANE" },
+                "plays": plays,
+                "maximum_guesses": maximum_guesses,
+                "difficulty": "Normal",
+                "puzzle": "Custom",
+            })
+        };
+
+        assert_eq!(
+            load(saved(&[("CRANE", "XXXXX"), ("SLOTH", "_____")], 6)),
+            "Invalid play 2: The game is already won."
+        );
+        assert_eq!(
+            load(saved(&[("SLOTH", "_____"), ("BRAWN", "_XX_O")], 1)),
+            "Invalid play 2: The game is over, there are no guesses left."
+        );
+        assert_eq!(
+            load(saved(&[("SLOTH", "XXXXX")], 6)),
+            "Invalid play 1: SLOTH scores _____, not XXXXX."
+        );
+        assert_eq!(
+            load(saved(&[("PLANET", "______")], 6)),
+            "Invalid play 1: Guess must be five letters long."
+        );
+        assert_eq!(
+            load(serde_json::json!({
+                "opponent": { "Adversary": [] },
+                "plays": [],
+                "maximum_guesses": 6,
+                "difficulty": "Normal",
+                "puzzle": "Adversarial",
+            })),
+            "An adversary needs at least one candidate."
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_letter_knowledge_round_trips_through_serde() {
+        let knowledge = game_with_predictions(Game::from_secret_word(word("CRANE")), &["BRAWN"])
+            .letter_knowledge();
+
+        let json = serde_json::to_value(&knowledge).unwrap();
+        assert_eq!(json["good"], serde_json::json!(["A", "N", "R"]));
+        assert_eq!(json["bad"], serde_json::json!(["B", "W"]));
+
+        let loaded: super::LetterKnowledge = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.good, knowledge.good);
+        assert_eq!(loaded.bad, knowledge.bad);
+        assert_eq!(loaded.unknown, knowledge.unknown);
+    }
 }
@@ -1,12 +1,57 @@
+pub mod config;
 pub mod dictionary;
 pub mod game;
+pub mod multi_game;
+pub mod share;
+pub mod solver;
+pub mod statistics;
+#[cfg(feature = "storage")]
+pub mod storage;
+pub mod strategy;
+#[cfg(test)]
+mod test_util;
 pub mod word;
 
+pub use crate::config::AnswerSelection;
+pub use crate::config::GameConfig;
+pub use crate::dictionary::daily_word;
 pub use crate::dictionary::random_word;
+pub use crate::dictionary::random_word_with;
+pub use crate::dictionary::DailyPuzzle;
+pub use crate::dictionary::Dictionary;
+pub use crate::dictionary::DictionaryError;
+pub use crate::game::Constraints;
+pub use crate::game::Difficulty;
 pub use crate::game::Game;
 pub use crate::game::GameStatus;
+pub use crate::game::LetterCount;
 pub use crate::game::LetterKnowledge;
+pub use crate::game::Opponent;
 pub use crate::game::Play;
+pub use crate::game::PlayError;
+pub use crate::game::Puzzle;
+pub use crate::multi_game::Board;
+pub use crate::multi_game::MultiGame;
+pub use crate::multi_game::MultiGameError;
+pub use crate::share::share_text;
+pub use crate::share::Palette;
+pub use crate::solver::Pattern;
+pub use crate::solver::Solver;
+pub use crate::solver::Suggestion;
+pub use crate::statistics::DistributionBar;
+pub use crate::statistics::GameRecord;
+pub use crate::statistics::Statistics;
+#[cfg(feature = "storage")]
+pub use crate::storage::SaveFile;
+#[cfg(feature = "storage")]
+pub use crate::storage::SavedGame;
+#[cfg(feature = "storage")]
+pub use crate::storage::StatisticsFile;
+#[cfg(feature = "storage")]
+pub use crate::storage::StorageError;
+pub use crate::strategy::Benchmark;
+pub use crate::strategy::Strategy;
+pub use crate::word::ScoreParseError;
 pub use crate::word::Word;
 pub use crate::word::WordParseError;
 pub use crate::word::WordScore;
@@ -0,0 +1,512 @@
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::config::{AnswerSelection, GameConfig};
+use crate::dictionary::Dictionary;
+use crate::game::{Constraints, Difficulty, GameStatus, LetterKnowledge, Play, PlayError};
+use crate::word::Word;
+
+/// One of the secret words of a [MultiGame], with the plays scored against it.
+#[derive(Clone, Debug)]
+pub struct Board {
+    pub secret_word: Word,
+    /// Plays stop once the board is solved.
+    pub plays: Vec<Play>,
+    pub constraints: Constraints,
+}
+
+impl Board {
+    pub fn new(secret_word: Word) -> Self {
+        let constraints = Constraints::new(secret_word.length());
+        Self {
+            secret_word,
+            plays: Vec::new(),
+            constraints,
+        }
+    }
+
+    /// Returns true once the secret word has been guessed.
+    pub fn is_solved(&self) -> bool {
+        self.plays.last().is_some_and(|play| play.score.is_winner())
+    }
+
+    pub fn letter_knowledge(&self) -> LetterKnowledge {
+        LetterKnowledge::from(&self.constraints)
+    }
+}
+
+/// Several secret words guessed at once, as in Dordle (2 boards), Quordle (4) or Octordle (8).
+/// Every guess is played on each board which is not solved yet, and scored separately.
+///
+/// ```rust
+/// use wordle::{GameStatus, MultiGame, Word};
+///
+/// let words: Vec<Word> = ["CRANE", "SLOTH"].iter().map(|s| s.parse().unwrap()).collect();
+/// let game = MultiGame::new(words);
+/// assert_eq!(game.remaining_guesses(), Some(7));
+///
+/// let game = game.with_prediction("CRANE".parse().unwrap());
+/// assert!(game.boards[0].is_solved());
+/// assert_eq!(game.boards[1].plays[0].score.to_string(), "_____");
+///
+/// let game = game.with_prediction("SLOTH".parse().unwrap());
+/// assert!(game.calculate_status() == GameStatus::Won);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiGame {
+    pub boards: Vec<Board>,
+    /// Every prediction played, in order.
+    pub predictions: Vec<Word>,
+    /// Guesses allowed before the game is lost, or `None` for unlimited guesses.
+    pub maximum_guesses: Option<usize>,
+    /// In hard mode, a guess must use the hints of every board it's played on.
+    pub difficulty: Difficulty,
+}
+
+impl MultiGame {
+    /// Guesses allowed on top of one per board.
+    pub const EXTRA_GUESSES: usize = 5;
+
+    /// Starts a game with one board per secret word, and [MultiGame::EXTRA_GUESSES] to spare.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no secret words, as a game without boards would already be won.
+    pub fn new(secret_words: Vec<Word>) -> Self {
+        assert!(!secret_words.is_empty(), "A game needs at least one board.");
+        let maximum_guesses = Some(secret_words.len() + Self::EXTRA_GUESSES);
+        Self {
+            boards: secret_words.into_iter().map(Board::new).collect(),
+            predictions: Vec::new(),
+            maximum_guesses,
+            difficulty: Difficulty::default(),
+        }
+    }
+
+    /// Starts a game with different random answers from the dictionary on each board.
+    /// There are fewer boards if the dictionary doesn't have enough answers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boards` is zero, see [MultiGame::new].
+    pub fn from_dictionary(dictionary: &Dictionary, boards: usize) -> Self {
+        Self::from_rng(dictionary, boards, &mut rand::thread_rng())
+    }
+
+    /// Starts a game with the rules and answer selection of the config, with different answers
+    /// on each board. There are fewer boards if the dictionary doesn't have enough answers.
+    /// The config's guess limit is for a single board, so one more guess is allowed per extra
+    /// board: the default of 6 leaves [MultiGame::EXTRA_GUESSES] to spare.
+    ///
+    /// ```rust
+    /// use wordle::{AnswerSelection, GameConfig, MultiGame, MultiGameError};
+    ///
+    /// let config = GameConfig::default().with_answer(AnswerSelection::Seeded(42));
+    /// let game = MultiGame::from_config(&config, 4).unwrap();
+    /// assert_eq!(game.boards[0].secret_word, config.dictionary.seeded_answer(42));
+    /// assert_eq!(game.maximum_guesses, Some(9));
+    ///
+    /// let config = config.with_answer(AnswerSelection::Adversarial);
+    /// let err = MultiGame::from_config(&config, 4).unwrap_err();
+    /// assert_eq!(err, MultiGameError::UnsupportedAnswer);
+    /// ```
+    pub fn from_config(config: &GameConfig, boards: usize) -> Result<Self, MultiGameError> {
+        if boards == 0 {
+            return Err(MultiGameError::NoBoards);
+        }
+
+        let dictionary = &config.dictionary;
+        let game = match &config.answer {
+            AnswerSelection::Random => Self::from_dictionary(dictionary, boards),
+            AnswerSelection::Seeded(seed) => Self::new(dictionary.seeded_answers(*seed, boards)),
+            AnswerSelection::Daily(puzzle) => Self::new(dictionary.daily_answers(*puzzle, boards)),
+            AnswerSelection::Adversarial | AnswerSelection::Word(_) => {
+                return Err(MultiGameError::UnsupportedAnswer)
+            }
+        };
+
+        let extra_boards = game.boards.len() - 1;
+        Ok(Self {
+            maximum_guesses: config
+                .maximum_guesses
+                .map(|maximum_guesses| maximum_guesses + extra_boards),
+            difficulty: config.difficulty,
+            ..game
+        })
+    }
+
+    /// Like [MultiGame::from_dictionary], using the given random number generator.
+    pub fn from_rng<R: Rng + ?Sized>(dictionary: &Dictionary, boards: usize, rng: &mut R) -> Self {
+        let secret_words = dictionary
+            .answers()
+            .choose_multiple(rng, boards)
+            .cloned()
+            .collect();
+        Self::new(secret_words)
+    }
+
+    /// Sets the number of guesses allowed, `None` meaning unlimited.
+    /// A game which has ended stays as it is, see [crate::Game::with_maximum_guesses].
+    pub fn with_maximum_guesses(&self, maximum_guesses: Option<usize>) -> Self {
+        let mut game = self.clone();
+        if game.calculate_status() == GameStatus::Active {
+            game.maximum_guesses = maximum_guesses;
+        }
+        game
+    }
+
+    pub fn with_difficulty(&self, difficulty: Difficulty) -> Self {
+        let mut game = self.clone();
+        game.difficulty = difficulty;
+        game
+    }
+
+    /// Plays a prediction, unless the game has ended, the prediction is the wrong length,
+    /// or in hard mode, it ignores a hint of a board which is not solved yet.
+    pub fn try_with_prediction(&self, prediction: Word) -> Result<Self, PlayError> {
+        match self.calculate_status() {
+            GameStatus::Active => {}
+            GameStatus::Won => return Err(PlayError::AlreadyWon),
+            GameStatus::Lost => return Err(PlayError::GameOver),
+        }
+
+        let expected = self.boards[0].secret_word.length();
+        if prediction.length() != expected {
+            return Err(PlayError::WrongLength { expected });
+        }
+
+        if self.difficulty == Difficulty::Hard {
+            let violation = self
+                .boards
+                .iter()
+                .filter(|board| !board.is_solved())
+                .find_map(|board| board.constraints.hard_mode_violation(&prediction));
+            if let Some(violation) = violation {
+                return Err(PlayError::HardModeViolation(violation));
+            }
+        }
+
+        Ok(self.with_prediction(prediction))
+    }
+
+    /// Plays a prediction on every board which is not solved yet,
+    /// without checking the rules of the game's [Difficulty].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the game has ended, or the prediction is not as long as the secret words.
+    pub fn with_prediction(&self, prediction: Word) -> Self {
+        assert!(
+            self.calculate_status() == GameStatus::Active,
+            "Can't play {} after the game has ended.",
+            prediction
+        );
+        assert_eq!(
+            prediction.length(),
+            self.boards[0].secret_word.length(),
+            "Prediction must be as long as the secret words."
+        );
+        let mut game = self.clone();
+
+        for board in game.boards.iter_mut().filter(|board| !board.is_solved()) {
+            let score = board.secret_word.guess(&prediction);
+            let play = Play {
+                prediction: prediction.clone(),
+                score,
+            };
+
+            board.constraints = board.constraints.update(&play);
+            board.plays.push(play);
+        }
+        game.predictions.push(prediction);
+
+        game
+    }
+
+    /// Guesses left before the game is lost, or `None` if guesses are unlimited.
+    pub fn remaining_guesses(&self) -> Option<usize> {
+        self.maximum_guesses
+            .map(|maximum_guesses| maximum_guesses.saturating_sub(self.predictions.len()))
+    }
+
+    /// Number of boards solved so far.
+    pub fn solved_count(&self) -> usize {
+        self.boards.iter().filter(|board| board.is_solved()).count()
+    }
+
+    /// Knowledge of the letters of the boards still to solve, combined for a shared keyboard.
+    /// See [LetterKnowledge::combine].
+    pub fn letter_knowledge(&self) -> LetterKnowledge {
+        let unsolved: Vec<LetterKnowledge> = self
+            .boards
+            .iter()
+            .filter(|board| !board.is_solved())
+            .map(Board::letter_knowledge)
+            .collect();
+        LetterKnowledge::combine(&unsolved)
+    }
+
+    pub fn calculate_status(&self) -> GameStatus {
+        if self.solved_count() == self.boards.len() {
+            GameStatus::Won
+        } else if self.remaining_guesses() == Some(0) {
+            GameStatus::Lost
+        } else {
+            GameStatus::Active
+        }
+    }
+}
+
+/// Reasons a [MultiGame] can't be started from a [GameConfig].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultiGameError {
+    /// A game needs at least one board.
+    NoBoards,
+    /// The config's answer selection can't choose a word for each board:
+    /// an adversary or a given word only makes sense on a single board.
+    UnsupportedAnswer,
+}
+
+impl fmt::Display for MultiGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoBoards => write!(f, "A game needs at least one board."),
+            Self::UnsupportedAnswer => write!(
+                f,
+                "Only random, seeded and daily answers can be played on several boards."
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use chrono::NaiveDate;
+
+    use crate::game::HardModeViolation;
+    use crate::test_util::word;
+    use crate::{
+        AnswerSelection, DailyPuzzle, Dictionary, Difficulty, GameConfig, GameStatus, PlayError,
+    };
+
+    use super::{MultiGame, MultiGameError};
+
+    fn game(secret_words: &[&str]) -> MultiGame {
+        MultiGame::new(secret_words.iter().map(|s| word(s)).collect())
+    }
+
+    #[test]
+    fn test_guess_limit_depends_on_boards() {
+        assert_eq!(game(&["CRANE", "SLOTH"]).maximum_guesses, Some(7));
+        assert_eq!(
+            game(&["CRANE", "SLOTH", "BUMPY", "FIELD"]).maximum_guesses,
+            Some(9)
+        );
+        assert_eq!(
+            game(&["CRANE", "SLOTH"])
+                .with_maximum_guesses(Some(3))
+                .remaining_guesses(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_guess_limit_from_config() {
+        let config = GameConfig::default();
+        let game = MultiGame::from_config(&config, 4).unwrap();
+        assert_eq!(game.maximum_guesses, Some(9));
+
+        let config = config
+            .with_word_length(6)
+            .unwrap()
+            .with_maximum_guesses(Some(8));
+        let game = MultiGame::from_config(&config, 2).unwrap();
+        assert_eq!(game.maximum_guesses, Some(9));
+        assert_eq!(game.boards[0].secret_word.length(), 6);
+
+        let config = config.with_maximum_guesses(None);
+        let game = MultiGame::from_config(&config, 2).unwrap();
+        assert_eq!(game.maximum_guesses, None);
+    }
+
+    fn secret_words(game: &MultiGame) -> Vec<String> {
+        game.boards
+            .iter()
+            .map(|board| board.secret_word.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_answers_from_config() {
+        let config = GameConfig::default().with_answer(AnswerSelection::Seeded(42));
+        let game = MultiGame::from_config(&config, 4).unwrap();
+        assert_eq!(
+            secret_words(&game),
+            secret_words(&MultiGame::from_config(&config, 4).unwrap())
+        );
+        assert_eq!(
+            game.boards[0].secret_word,
+            config.dictionary.seeded_answer(42)
+        );
+
+        let puzzle = DailyPuzzle::new(NaiveDate::from_ymd_opt(2022, 3, 1).unwrap());
+        let config = config.with_answer(AnswerSelection::Daily(puzzle));
+        let game = MultiGame::from_config(&config, 2).unwrap();
+        assert_eq!(
+            game.boards[0].secret_word,
+            config.dictionary.daily_answers(puzzle, 2)[0]
+        );
+        assert_eq!(
+            secret_words(&game),
+            secret_words(&MultiGame::from_config(&config, 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_config_without_answers_for_each_board_is_rejected() {
+        let config = GameConfig::default();
+        assert_eq!(
+            MultiGame::from_config(&config, 0).unwrap_err(),
+            MultiGameError::NoBoards
+        );
+
+        for answer in [
+            AnswerSelection::Adversarial,
+            AnswerSelection::Word(word("CRANE")),
+        ] {
+            let config = config.with_answer(answer);
+            assert_eq!(
+                MultiGame::from_config(&config, 2).unwrap_err(),
+                MultiGameError::UnsupportedAnswer
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one board")]
+    fn test_game_without_boards_panics() {
+        MultiGame::new(Vec::new());
+    }
+
+    #[test]
+    fn test_hard_mode_uses_hints_of_unsolved_boards() {
+        let config = GameConfig::default().with_difficulty(Difficulty::Hard);
+        let from_config = MultiGame::from_config(&config, 2).unwrap();
+        assert_eq!(from_config.difficulty, Difficulty::Hard);
+
+        let game = game(&["CRANE", "SLOTH"])
+            .with_difficulty(Difficulty::Hard)
+            .with_prediction(word("CLOTH"));
+        assert_eq!(
+            game.try_with_prediction(word("BUMPY")).unwrap_err(),
+            PlayError::HardModeViolation(HardModeViolation::MissingPlacedLetter {
+                position: 0,
+                letter: 'C'
+            })
+        );
+        assert!(game.try_with_prediction(word("SLOTH")).is_err());
+
+        // Once SLOTH is solved, only the hints of CRANE count
+        let game = game
+            .with_difficulty(Difficulty::Normal)
+            .with_prediction(word("SLOTH"))
+            .with_difficulty(Difficulty::Hard);
+        assert!(game.try_with_prediction(word("CRANE")).is_ok());
+    }
+
+    #[test]
+    fn test_solved_boards_take_no_more_plays() {
+        let game = game(&["CRANE", "SLOTH"])
+            .with_prediction(word("CRANE"))
+            .with_prediction(word("BUMPY"));
+
+        assert_eq!(game.predictions.len(), 2);
+        assert_eq!(game.boards[0].plays.len(), 1);
+        assert_eq!(game.boards[1].plays.len(), 2);
+        assert_eq!(game.solved_count(), 1);
+        assert!(game.calculate_status() == GameStatus::Active);
+    }
+
+    #[test]
+    fn test_game_lost_when_out_of_guesses() {
+        let game = (0..7).fold(game(&["CRANE", "SLOTH"]), |game, _| {
+            game.with_prediction(word("CRANE"))
+        });
+
+        assert_eq!(game.remaining_guesses(), Some(0));
+        assert!(game.calculate_status() == GameStatus::Lost);
+
+        // A lost game can't be resumed
+        let game = game.with_maximum_guesses(None);
+        assert_eq!(game.remaining_guesses(), Some(0));
+        assert!(game.calculate_status() == GameStatus::Lost);
+    }
+
+    #[test]
+    fn test_game_rejects_plays_after_the_end() {
+        let won = game(&["CRANE", "SLOTH"])
+            .with_prediction(word("CRANE"))
+            .with_prediction(word("SLOTH"));
+        assert_eq!(
+            won.try_with_prediction(word("BUMPY")).unwrap_err(),
+            PlayError::AlreadyWon
+        );
+
+        let lost = game(&["CRANE", "SLOTH"]).with_maximum_guesses(Some(1));
+        let lost = lost.try_with_prediction(word("BUMPY")).unwrap();
+        assert_eq!(
+            lost.try_with_prediction(word("SLOTH")).unwrap_err(),
+            PlayError::GameOver
+        );
+        assert_eq!(lost.predictions.len(), 1);
+
+        assert_eq!(
+            game(&["CRANE", "SLOTH"])
+                .try_with_prediction(word("CRANES"))
+                .unwrap_err(),
+            PlayError::WrongLength { expected: 5 }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "after the game has ended")]
+    fn test_with_prediction_panics_after_the_end() {
+        game(&["CRANE"])
+            .with_prediction(word("CRANE"))
+            .with_prediction(word("SLOTH"));
+    }
+
+    #[test]
+    fn test_combined_letter_knowledge() {
+        let game = game(&["CRANE", "SLOTH", "BUMPY"]).with_prediction(word("CLOTH"));
+
+        let knowledge = game.letter_knowledge();
+
+        // Letters found on one board are good, even where other boards rule them out
+        assert_eq!(knowledge.good, BTreeSet::from(['C', 'H', 'L', 'O', 'T']));
+        assert!(knowledge.bad.is_empty());
+
+        // Once SLOTH is solved, only CRANE and BUMPY count
+        let game = game.with_prediction(word("SLOTH"));
+        let knowledge = game.letter_knowledge();
+        assert_eq!(knowledge.good, BTreeSet::from(['C']));
+        assert_eq!(knowledge.bad, BTreeSet::from(['H', 'L', 'O', 'S', 'T']));
+    }
+
+    #[test]
+    fn test_boards_get_different_answers() {
+        let dictionary = Dictionary::from_reader("CRANE\nSLOTH\nBUMPY\n".as_bytes()).unwrap();
+
+        let game = MultiGame::from_dictionary(&dictionary, 3);
+        let secret_words: BTreeSet<String> = game
+            .boards
+            .iter()
+            .map(|board| board.secret_word.to_string())
+            .collect();
+        assert_eq!(secret_words.len(), 3);
+    }
+}
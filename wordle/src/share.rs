@@ -0,0 +1,178 @@
+use crate::game::{Difficulty, Game, GameStatus, Puzzle};
+use crate::word::LetterScore;
+
+/// Colors of the tiles in [share_text], and in the command line game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    /// Green for letters in the right spot, yellow for letters elsewhere in the word.
+    #[default]
+    Standard,
+    /// Orange and blue instead of green and yellow, for players who can't tell those apart.
+    HighContrast,
+}
+
+impl Palette {
+    /// The emoji tile for a letter's score.
+    pub fn tile(&self, letter_score: LetterScore) -> char {
+        match (self, letter_score) {
+            (Self::Standard, LetterScore::PlacedCorrectly) => '🟩',
+            (Self::Standard, LetterScore::PresentElsewhere) => '🟨',
+            (Self::HighContrast, LetterScore::PlacedCorrectly) => '🟧',
+            (Self::HighContrast, LetterScore::PresentElsewhere) => '🟦',
+            (_, LetterScore::NotPresent) => '⬛',
+        }
+    }
+}
+
+/// A spoiler-free summary of a finished game to paste into a chat: a header with the puzzle
+/// and the number of guesses, then one row of tiles per play. Returns nothing while the game
+/// is still in progress.
+///
+/// The header shows "X" instead of the number of guesses for a lost game,
+/// and ends with an asterisk in hard mode.
+///
+/// ```rust
+/// use wordle::{share_text, Game, Palette};
+///
+/// let game = Game::from_secret_word("CRANE".parse().unwrap())
+///     .with_prediction("BRAWN".parse().unwrap())
+///     .with_prediction("CRANE".parse().unwrap());
+///
+/// assert_eq!(
+///     share_text(&game, Palette::Standard).unwrap(),
+///     "Wordle 2/6\n⬛🟩🟩⬛🟨\n🟩🟩🟩🟩🟩"
+/// );
+/// ```
+pub fn share_text(game: &Game, palette: Palette) -> Option<String> {
+    let guesses = match game.calculate_status() {
+        GameStatus::Active => return None,
+        GameStatus::Won => game.plays().len().to_string(),
+        GameStatus::Lost => "X".to_string(),
+    };
+    let maximum_guesses = match game.maximum_guesses() {
+        Some(maximum_guesses) => maximum_guesses.to_string(),
+        None => "∞".to_string(),
+    };
+    let hard_mode = match game.difficulty {
+        Difficulty::Hard => "*",
+        Difficulty::Normal => "",
+    };
+
+    let mut text = format!(
+        "{} {}/{}{}",
+        puzzle_name(game.puzzle),
+        guesses,
+        maximum_guesses,
+        hard_mode
+    );
+    for play in game.plays() {
+        text.push('\n');
+        text.extend(
+            play.score
+                .0
+                .iter()
+                .map(|&letter_score| palette.tile(letter_score)),
+        );
+    }
+
+    Some(text)
+}
+
+/// The start of the header, e.g. "Wordle 1,234" for a daily puzzle, so players can compare
+/// games of the same puzzle.
+fn puzzle_name(puzzle: Puzzle) -> String {
+    match puzzle {
+        Puzzle::Custom => "Wordle".to_string(),
+        Puzzle::Seeded(seed) => format!("Wordle (seed {})", seed),
+        Puzzle::Daily(daily_puzzle) => format!("Wordle {}", group_thousands(daily_puzzle.number)),
+        Puzzle::Adversarial => "Wordle (absurd)".to_string(),
+    }
+}
+
+/// Formats a number with commas between groups of three digits, e.g. "1,234".
+fn group_thousands(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    if n < 0 {
+        format!("-{}", grouped)
+    } else {
+        grouped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::test_util::{game_with_predictions, word};
+    use crate::{DailyPuzzle, Dictionary, Difficulty, Game};
+
+    use super::{group_thousands, share_text, Palette};
+
+    #[test]
+    fn test_no_share_text_while_playing() {
+        let game = game_with_predictions(Game::from_secret_word(word("CRANE")), &["SLOTH"]);
+        assert!(share_text(&game, Palette::Standard).is_none());
+    }
+
+    #[test]
+    fn test_share_lost_game_in_hard_mode() {
+        let game = Game::from_secret_word(word("CRANE"))
+            .with_difficulty(Difficulty::Hard)
+            .with_maximum_guesses(Some(2));
+        let game = game_with_predictions(game, &["SLOTH", "BRAWN"]);
+
+        assert_eq!(
+            share_text(&game, Palette::Standard).unwrap(),
+            "Wordle X/2*\n⬛⬛⬛⬛⬛\n⬛🟩🟩⬛🟨"
+        );
+        assert_eq!(
+            share_text(&game, Palette::HighContrast).unwrap(),
+            "Wordle X/2*\n⬛⬛⬛⬛⬛\n⬛🟧🟧⬛🟦"
+        );
+    }
+
+    #[test]
+    fn test_share_daily_puzzle() {
+        let dictionary = Dictionary::from_reader("CRANE\n".as_bytes()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 10, 13).unwrap();
+        let game = Game::daily(&dictionary, DailyPuzzle::new(date)).with_maximum_guesses(None);
+        let game = game_with_predictions(game, &["CRANE"]);
+
+        assert_eq!(
+            share_text(&game, Palette::Standard).unwrap(),
+            "Wordle 1,212 1/∞\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn test_share_seeded_and_absurd_games() {
+        let dictionary = Dictionary::from_reader("CRANE\nSLOTH\n".as_bytes()).unwrap();
+
+        let game = Game::seeded(&dictionary, 42);
+        let game = game.with_prediction(game.secret_word().clone());
+        let text = share_text(&game, Palette::Standard).unwrap();
+        assert!(text.starts_with("Wordle (seed 42) 1/6\n"));
+
+        let game = game_with_predictions(Game::adversarial(&dictionary), &["CRANE", "SLOTH"]);
+        let text = share_text(&game, Palette::Standard).unwrap();
+        assert!(text.starts_with("Wordle (absurd) 2/6\n"));
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(123), "123");
+        assert_eq!(group_thousands(1234), "1,234");
+        assert_eq!(group_thousands(1234567), "1,234,567");
+        assert_eq!(group_thousands(-1234), "-1,234");
+    }
+}
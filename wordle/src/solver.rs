@@ -0,0 +1,560 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use rayon::prelude::*;
+
+use crate::dictionary::Dictionary;
+use crate::game::{Constraints, Difficulty, Game, Play};
+use crate::word::{LetterScore, Word, WordScore};
+
+/// A [WordScore] packed into a small number, so that scores can be stored in bulk and counted.
+///
+/// Each letter is one base-3 digit, the first letter being the least significant:
+/// 0 for [LetterScore::NotPresent], 1 for [LetterScore::PresentElsewhere],
+/// and 2 for [LetterScore::PlacedCorrectly].
+///
+/// ```rust
+/// use wordle::{Pattern, Word};
+///
+/// let secret: Word = "ABIDE".parse().unwrap();
+/// let prediction: Word = "SPEED".parse().unwrap();
+///
+/// assert_eq!(Pattern::new(&prediction, &secret), Pattern::from(&secret.guess(&prediction)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pattern(u32);
+
+impl Pattern {
+    /// Number of distinct patterns for words of the given length, e.g. 243 for five letters.
+    pub fn count(word_length: usize) -> usize {
+        3usize.pow(word_length as u32)
+    }
+
+    /// Scores a prediction against the secret word, like [Word::guess].
+    /// Both words must be the same length.
+    pub fn new(prediction: &Word, secret: &Word) -> Self {
+        Self::from_letters(prediction.as_bytes(), secret.as_bytes())
+    }
+
+    /// Same algorithm as [Word::guess], working on bytes to avoid allocating.
+    fn from_letters(prediction: &[u8], secret: &[u8]) -> Self {
+        let word_length = secret.len();
+        let mut digits = [NOT_PRESENT; Word::MAX_LENGTH];
+        let mut unmatched = [0u8; 26];
+
+        // First pass: letters in the correct spot
+        for position in 0..word_length {
+            if prediction[position] == secret[position] {
+                digits[position] = PLACED_CORRECTLY;
+            } else {
+                unmatched[(secret[position] - b'A') as usize] += 1;
+            }
+        }
+
+        // Second pass: letters elsewhere in the word, while unmatched copies remain
+        for position in 0..word_length {
+            let count = &mut unmatched[(prediction[position] - b'A') as usize];
+            if digits[position] != PLACED_CORRECTLY && *count > 0 {
+                digits[position] = PRESENT_ELSEWHERE;
+                *count -= 1;
+            }
+        }
+
+        Self::from_digits(digits[..word_length].iter().copied())
+    }
+
+    fn from_digits<I>(digits: I) -> Self
+    where
+        I: IntoIterator<Item = u8>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        Pattern(
+            digits
+                .into_iter()
+                .rev()
+                .fold(0, |code, digit| code * 3 + digit as u32),
+        )
+    }
+}
+
+const NOT_PRESENT: u8 = 0;
+const PRESENT_ELSEWHERE: u8 = 1;
+const PLACED_CORRECTLY: u8 = 2;
+
+impl From<&WordScore> for Pattern {
+    fn from(score: &WordScore) -> Self {
+        let digits = score.0.iter().map(|letter_score| match letter_score {
+            LetterScore::NotPresent => NOT_PRESENT,
+            LetterScore::PresentElsewhere => PRESENT_ELSEWHERE,
+            LetterScore::PlacedCorrectly => PLACED_CORRECTLY,
+        });
+        Self::from_digits(digits)
+    }
+}
+
+/// A guess ranked by the [Solver].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub word: Word,
+    /// Expected information from playing the word, in bits.
+    /// Each bit halves the number of remaining candidates, on average.
+    pub entropy: f64,
+    /// Whether the word could be the answer, so that playing it might win.
+    pub is_candidate: bool,
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:.2} bits)", self.word, self.entropy)
+    }
+}
+
+/// Ranks guesses by their expected information (Shannon entropy) about the answer.
+///
+/// Scoring every allowed guess against every answer is done once, up front,
+/// so that ranking guesses for a game only counts patterns in a table.
+///
+/// ```rust
+/// use wordle::{Dictionary, Game, Solver};
+///
+/// let answers = "CATCH\nHATCH\nMATCH\nPATCH\n";
+/// let allowed = "CHOMP\n";
+/// let dictionary = Dictionary::from_readers(answers.as_bytes(), allowed.as_bytes()).unwrap();
+/// let solver = Solver::new(&dictionary);
+///
+/// let game = Game::from_secret_word("MATCH".parse().unwrap());
+/// let best = &solver.suggestions(&game)[0];
+///
+/// // CHOMP tells the four answers apart, gaining two bits
+/// assert_eq!(best.word.to_string(), "CHOMP");
+/// assert_eq!(best.entropy, 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Solver {
+    word_length: usize,
+    /// Every allowed guess, sorted alphabetically.
+    guesses: Vec<Word>,
+    answers: Vec<Word>,
+    /// Position of each word in `guesses`.
+    guess_indices: HashMap<Word, usize>,
+    patterns: PatternTable,
+    /// The best first guess, once worked out.
+    opening: OnceLock<Option<Word>>,
+}
+
+/// The pattern of every guess against every answer, one row per guess.
+/// Patterns of words up to five letters fit in a byte, which keeps the table four times smaller.
+#[derive(Clone, Debug)]
+enum PatternTable {
+    Narrow(Vec<u8>),
+    Wide(Vec<u32>),
+}
+
+impl PatternTable {
+    /// Longest words whose patterns fit in a byte.
+    const NARROW_MAX_LENGTH: usize = 5;
+
+    /// Scores the guesses in parallel, as there are millions of patterns to compute.
+    fn new(word_length: usize, guesses: &[Word], answers: &[Word]) -> Self {
+        let patterns = guesses.par_iter().flat_map_iter(|guess| {
+            answers
+                .iter()
+                .map(|answer| Pattern::from_letters(guess.as_bytes(), answer.as_bytes()).0)
+        });
+
+        if word_length <= Self::NARROW_MAX_LENGTH {
+            Self::Narrow(patterns.map(|code| code as u8).collect())
+        } else {
+            Self::Wide(patterns.collect())
+        }
+    }
+
+    fn get(&self, index: usize) -> Pattern {
+        match self {
+            Self::Narrow(patterns) => Pattern(patterns[index] as u32),
+            Self::Wide(patterns) => Pattern(patterns[index]),
+        }
+    }
+}
+
+/// A [Suggestion] referring to a guess by its position.
+struct Ranking {
+    guess_index: usize,
+    entropy: f64,
+    is_candidate: bool,
+}
+
+impl Ranking {
+    /// Orders the best guesses first: by entropy, then candidates, then alphabetically.
+    fn compare(a: &Ranking, b: &Ranking) -> Ordering {
+        b.entropy
+            .total_cmp(&a.entropy)
+            .then(b.is_candidate.cmp(&a.is_candidate))
+            .then(a.guess_index.cmp(&b.guess_index))
+    }
+}
+
+impl Solver {
+    /// Precomputes the pattern of every allowed guess against every answer, so that ranking
+    /// guesses is fast. This is the slow part: over a second on a single core for the builtin
+    /// five-letter lists, so the table is built on all cores.
+    pub fn new(dictionary: &Dictionary) -> Self {
+        let mut guesses: Vec<Word> = dictionary.allowed().cloned().collect();
+        guesses.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+        let answers = dictionary.answers().to_vec();
+
+        let guess_indices = guesses
+            .iter()
+            .enumerate()
+            .map(|(index, word)| (word.clone(), index))
+            .collect();
+
+        let word_length = dictionary.word_length();
+        let patterns = PatternTable::new(word_length, &guesses, &answers);
+
+        Self {
+            word_length,
+            guesses,
+            answers,
+            guess_indices,
+            patterns,
+            opening: OnceLock::new(),
+        }
+    }
+
+    /// Number of letters in the words the solver plays with.
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    /// The answers which agree with the scores of every play in the game.
+    pub fn candidates(&self, game: &Game) -> Vec<&Word> {
+        self.candidates_after(game.plays())
+    }
+
+    /// The answers which agree with the scores of every play,
+    /// e.g. for plays scored by a game outside this crate.
+    pub fn candidates_after(&self, plays: &[Play]) -> Vec<&Word> {
+        self.candidate_indices(plays)
+            .into_iter()
+            .map(|index| &self.answers[index])
+            .collect()
+    }
+
+    /// Every allowed guess, best first.
+    ///
+    /// Guesses are ranked by entropy, preferring candidates on ties since they might win outright.
+    /// In hard mode, guesses which break the rules are left out.
+    pub fn suggestions(&self, game: &Game) -> Vec<Suggestion> {
+        self.suggestions_after(game.plays(), game.difficulty)
+    }
+
+    /// Every allowed guess after the given plays, best first. See [Solver::suggestions].
+    pub fn suggestions_after(&self, plays: &[Play], difficulty: Difficulty) -> Vec<Suggestion> {
+        let candidates = self.candidate_indices(plays);
+        let mut rankings = self.rankings(plays, &candidates, difficulty);
+        rankings.sort_by(Ranking::compare);
+
+        rankings
+            .into_iter()
+            .map(|ranking| Suggestion {
+                word: self.guesses[ranking.guess_index].clone(),
+                entropy: ranking.entropy,
+                is_candidate: ranking.is_candidate,
+            })
+            .collect()
+    }
+
+    /// The first of [Solver::suggestions], without ranking every guess in full.
+    /// Returns nothing when no allowed guess follows the hard mode rules.
+    pub fn best_guess(&self, game: &Game) -> Option<Word> {
+        // The opening is the same for every game, so it is only worked out once
+        if game.plays().is_empty() {
+            return self
+                .opening
+                .get_or_init(|| self.best_guess_after(&[], Difficulty::Normal))
+                .clone();
+        }
+
+        self.best_guess_after(game.plays(), game.difficulty)
+    }
+
+    fn best_guess_after(&self, plays: &[Play], difficulty: Difficulty) -> Option<Word> {
+        let candidates = self.candidate_indices(plays);
+
+        // Nothing beats playing a candidate when there are at most two,
+        // and candidates always follow the hard mode rules
+        if let 1..=2 = candidates.len() {
+            return candidates
+                .iter()
+                .map(|&answer_index| &self.answers[answer_index])
+                .min_by_key(|word| self.guess_indices[*word])
+                .cloned();
+        }
+
+        self.rankings(plays, &candidates, difficulty)
+            .into_iter()
+            .min_by(Ranking::compare)
+            .map(|ranking| self.guesses[ranking.guess_index].clone())
+    }
+
+    /// Scores every guess which follows the rules, in no particular order.
+    fn rankings(
+        &self,
+        plays: &[Play],
+        candidates: &[usize],
+        difficulty: Difficulty,
+    ) -> Vec<Ranking> {
+        let constraints = plays
+            .iter()
+            .fold(Constraints::new(self.word_length), |constraints, play| {
+                constraints.update(play)
+            });
+
+        let mut is_candidate = vec![false; self.guesses.len()];
+        for &answer_index in candidates {
+            is_candidate[self.guess_indices[&self.answers[answer_index]]] = true;
+        }
+
+        let mut counts = PatternCounts::new(self.word_length);
+
+        self.guesses
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| {
+                difficulty == Difficulty::Normal || constraints.hard_mode_violation(word).is_none()
+            })
+            .map(|(guess_index, _)| Ranking {
+                guess_index,
+                entropy: self.entropy(guess_index, candidates, &mut counts),
+                is_candidate: is_candidate[guess_index],
+            })
+            .collect()
+    }
+
+    fn candidate_indices(&self, plays: &[Play]) -> Vec<usize> {
+        let mut candidates: Vec<usize> = (0..self.answers.len()).collect();
+
+        for play in plays {
+            // No answer agrees with a play of another length
+            if play.prediction.length() != self.word_length
+                || play.score.0.len() != self.word_length
+            {
+                return Vec::new();
+            }
+
+            let expected = Pattern::from(&play.score);
+
+            match self.guess_indices.get(&play.prediction) {
+                Some(&guess_index) => {
+                    candidates.retain(|&index| self.pattern(guess_index, index) == expected)
+                }
+                // Guesses outside the dictionary are scored on the fly
+                None => candidates.retain(|&index| {
+                    Pattern::new(&play.prediction, &self.answers[index]) == expected
+                }),
+            }
+        }
+
+        candidates
+    }
+
+    fn pattern(&self, guess_index: usize, answer_index: usize) -> Pattern {
+        self.patterns
+            .get(guess_index * self.answers.len() + answer_index)
+    }
+
+    /// Shannon entropy of the distribution of patterns a guess produces over the candidates.
+    fn entropy(&self, guess_index: usize, candidates: &[usize], counts: &mut PatternCounts) -> f64 {
+        for &answer_index in candidates {
+            counts.add(self.pattern(guess_index, answer_index));
+        }
+
+        let total = candidates.len() as f64;
+        counts
+            .drain()
+            .map(|count| {
+                let probability = count as f64 / total;
+                -probability * probability.log2()
+            })
+            .sum()
+    }
+}
+
+/// Tallies patterns, remembering which were seen so that clearing is cheap.
+/// Guesses usually leave few candidates, producing only a handful of the possible patterns.
+struct PatternCounts {
+    counts: Vec<u32>,
+    seen: Vec<Pattern>,
+}
+
+impl PatternCounts {
+    fn new(word_length: usize) -> Self {
+        Self {
+            counts: vec![0; Pattern::count(word_length)],
+            seen: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, pattern: Pattern) {
+        let count = &mut self.counts[pattern.0 as usize];
+        if *count == 0 {
+            self.seen.push(pattern);
+        }
+        *count += 1;
+    }
+
+    /// The counts of the patterns seen, resetting them to zero.
+    fn drain(&mut self) -> impl Iterator<Item = u32> + '_ {
+        let counts = &mut self.counts;
+        self.seen
+            .drain(..)
+            .map(move |pattern| std::mem::take(&mut counts[pattern.0 as usize]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::word;
+    use crate::{Dictionary, Difficulty, Game};
+
+    use super::{Pattern, Solver};
+
+    fn solver(answers: &str, allowed: &str) -> Solver {
+        Solver::new(&Dictionary::from_readers(answers.as_bytes(), allowed.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_pattern_agrees_with_word_score() {
+        let words = [
+            "ABIDE", "SPEED", "EERIE", "CREPE", "GEESE", "ELDER", "LLAMA", "ALLOY", "ROBOT",
+            "FLOOR", "CRANE", "QUEUE",
+        ];
+
+        for secret in words {
+            for prediction in words {
+                let (secret, prediction) = (word(secret), word(prediction));
+                assert_eq!(
+                    Pattern::new(&prediction, &secret),
+                    Pattern::from(&secret.guess(&prediction)),
+                    "{} against {}",
+                    prediction,
+                    secret
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_winning_pattern_is_the_largest() {
+        for secret in ["LION", "CRANE", "PRESTIGIOUS"] {
+            let secret = word(secret);
+            assert_eq!(
+                Pattern::new(&secret, &secret).0 as usize,
+                Pattern::count(secret.length()) - 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_solves_longer_words() {
+        let solver = solver("CATCHES\nHATCHES\nMATCHES\nPATCHES\n", "CHOMPED\n");
+        assert_eq!(solver.word_length(), 7);
+
+        let game = Game::from_secret_word(word("PATCHES"));
+        assert_eq!(solver.best_guess(&game), Some(word("CHOMPED")));
+
+        let game = game.with_prediction(word("CHOMPED"));
+        assert_eq!(solver.candidates(&game), vec![&word("PATCHES")]);
+
+        // Plays of another length rule out every answer
+        let game = Game::from_secret_word(word("CRANE")).with_prediction(word("CRANE"));
+        assert!(solver.candidates(&game).is_empty());
+    }
+
+    #[test]
+    fn test_candidates_agree_with_plays() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "CHOMP\n");
+
+        let game = Game::from_secret_word(word("MATCH")).with_prediction(word("CHOMP"));
+        assert_eq!(solver.candidates(&game), vec![&word("MATCH")]);
+
+        let game = Game::from_secret_word(word("HATCH")).with_prediction(word("CATCH"));
+        assert_eq!(
+            solver.candidates(&game),
+            vec![&word("HATCH"), &word("MATCH"), &word("PATCH")]
+        );
+    }
+
+    #[test]
+    fn test_candidates_from_guesses_outside_dictionary() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "");
+
+        let game = Game::from_secret_word(word("PATCH")).with_prediction(word("PLUMB"));
+        assert_eq!(solver.candidates(&game), vec![&word("PATCH")]);
+    }
+
+    #[test]
+    fn test_suggestions_prefer_candidates_on_ties() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "CHOMP\nZZZZZ\n");
+
+        let game = Game::from_secret_word(word("MATCH")).with_prediction(word("CHOMP"));
+        let suggestions = solver.suggestions(&game);
+
+        // One candidate left, so nothing can be learned, but MATCH wins
+        assert_eq!(suggestions[0].word, word("MATCH"));
+        assert!(suggestions[0].is_candidate);
+        assert!(suggestions
+            .iter()
+            .all(|suggestion| suggestion.entropy == 0.0));
+    }
+
+    #[test]
+    fn test_best_guess_is_first_suggestion() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "CHOMP\nZZZZZ\n");
+
+        let new_game = Game::from_secret_word(word("MATCH"));
+        let played_game = new_game.with_prediction(word("CATCH"));
+
+        for game in [&new_game, &played_game, &new_game] {
+            let best = solver.best_guess(game).unwrap();
+            assert_eq!(best, solver.suggestions(game)[0].word);
+        }
+    }
+
+    #[test]
+    fn test_suggestions_ranked_by_entropy() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "CHOMP\n");
+
+        let game = Game::from_secret_word(word("HATCH"));
+        let suggestions = solver.suggestions(&game);
+
+        assert_eq!(suggestions[0].word, word("CHOMP"));
+        assert!(!suggestions[0].is_candidate);
+        assert!(suggestions
+            .windows(2)
+            .all(|pair| pair[0].entropy >= pair[1].entropy));
+
+        // Each answer singles itself out, leaving the other three together
+        let expected = -(0.25f64 * 0.25f64.log2() + 0.75 * 0.75f64.log2());
+        assert!((suggestions[1].entropy - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hard_mode_suggestions_follow_rules() {
+        let solver = solver("CATCH\nHATCH\nMATCH\nPATCH\n", "CHOMP\nBLURT\n");
+
+        let game = Game::from_secret_word(word("HATCH"))
+            .with_difficulty(Difficulty::Hard)
+            .with_prediction(word("CATCH"));
+        let suggestions = solver.suggestions(&game);
+
+        // ATCH must stay in place, ruling out CHOMP and BLURT
+        assert!(suggestions
+            .iter()
+            .all(|suggestion| suggestion.word.to_string().ends_with("ATCH")));
+    }
+}
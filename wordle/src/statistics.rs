@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::game::{Difficulty, Game, GameStatus, Puzzle};
+use crate::word::Word;
+
+/// One finished game, as kept in the player's [Statistics].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    /// The day the game ended.
+    pub date: NaiveDate,
+    pub answer: Word,
+    /// Number of guesses played, including the winning one.
+    pub guesses: usize,
+    pub won: bool,
+    pub difficulty: Difficulty,
+    pub puzzle: Puzzle,
+}
+
+impl GameRecord {
+    /// Records a game which has ended, or `None` if it is still in progress.
+    pub fn new(game: &Game, date: NaiveDate) -> Option<Self> {
+        let won = match game.calculate_status() {
+            GameStatus::Active => return None,
+            GameStatus::Won => true,
+            GameStatus::Lost => false,
+        };
+
+        Some(Self {
+            date,
+            answer: game.secret_word().clone(),
+            guesses: game.plays().len(),
+            won,
+            difficulty: game.difficulty,
+            puzzle: game.puzzle,
+        })
+    }
+}
+
+/// Every game the player has finished, and the totals worked out from them.
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use wordle::{Game, GameRecord, Statistics};
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+/// let won = Game::from_secret_word("CRANE".parse().unwrap())
+///     .with_prediction("SLOTH".parse().unwrap())
+///     .with_prediction("CRANE".parse().unwrap());
+///
+/// let mut statistics = Statistics::default();
+/// statistics.record(GameRecord::new(&won, date).unwrap());
+///
+/// assert_eq!(statistics.games_played(), 1);
+/// assert_eq!(statistics.win_percentage(), 100.0);
+/// assert_eq!(statistics.current_streak(), 1);
+/// assert_eq!(statistics.guess_distribution()[&2], 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// One record per game, oldest first.
+    pub records: Vec<GameRecord>,
+}
+
+impl Statistics {
+    /// Length of the longest bar in charts of the guess distribution.
+    pub const BAR_WIDTH: usize = 20;
+
+    /// Adds a game after every game recorded so far.
+    pub fn record(&mut self, record: GameRecord) {
+        self.records.push(record);
+    }
+
+    pub fn games_played(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn games_won(&self) -> usize {
+        self.records.iter().filter(|record| record.won).count()
+    }
+
+    /// Share of the games played which were won, from 0 to 100.
+    pub fn win_percentage(&self) -> f64 {
+        if self.records.is_empty() {
+            return 0.0;
+        }
+        100.0 * self.games_won() as f64 / self.games_played() as f64
+    }
+
+    /// Number of games won in a row, up to the last game played.
+    pub fn current_streak(&self) -> usize {
+        self.records
+            .iter()
+            .rev()
+            .take_while(|record| record.won)
+            .count()
+    }
+
+    /// Most games won in a row.
+    pub fn max_streak(&self) -> usize {
+        self.records
+            .split(|record| !record.won)
+            .map(<[GameRecord]>::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of games won with each number of guesses,
+    /// from one guess up to [crate::GameConfig::DEFAULT_MAXIMUM_GUESSES] or the longest win.
+    pub fn guess_distribution(&self) -> BTreeMap<usize, usize> {
+        let longest = self
+            .records
+            .iter()
+            .filter(|record| record.won)
+            .map(|record| record.guesses)
+            .max()
+            .unwrap_or(0)
+            .max(crate::GameConfig::DEFAULT_MAXIMUM_GUESSES);
+
+        let mut distribution: BTreeMap<usize, usize> = (1..=longest).map(|n| (n, 0)).collect();
+        for record in self.records.iter().filter(|record| record.won) {
+            *distribution.entry(record.guesses).or_default() += 1;
+        }
+        distribution
+    }
+
+    /// The bars of a chart of the [Statistics::guess_distribution], the longest being `width`
+    /// long. A bar for any wins is at least one long.
+    pub fn distribution_bars(&self, width: usize) -> Vec<DistributionBar> {
+        let distribution = self.guess_distribution();
+        let most = distribution.values().copied().max().unwrap_or(0).max(1);
+
+        distribution
+            .into_iter()
+            .map(|(guesses, wins)| DistributionBar {
+                guesses,
+                wins,
+                length: (wins * width).div_ceil(most),
+            })
+            .collect()
+    }
+}
+
+/// One bar of a chart of the guess distribution, see [Statistics::distribution_bars].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DistributionBar {
+    pub guesses: usize,
+    /// Number of games won with this many guesses.
+    pub wins: usize,
+    pub length: usize,
+}
+
+impl fmt::Display for Statistics {
+    /// A summary with a bar chart of the guess distribution.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Played: {}", self.games_played())?;
+        writeln!(f, "Win %: {:.0}", self.win_percentage())?;
+        writeln!(f, "Current streak: {}", self.current_streak())?;
+        writeln!(f, "Max streak: {}", self.max_streak())?;
+
+        write!(f, "Guess distribution:")?;
+        for bar in self.distribution_bars(Self::BAR_WIDTH) {
+            write!(f, "\n  {}: ", bar.guesses)?;
+            if bar.length > 0 {
+                write!(f, "{} ", "#".repeat(bar.length))?;
+            }
+            write!(f, "{}", bar.wins)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::test_util::word;
+    use crate::Game;
+
+    use super::{DistributionBar, GameRecord, Statistics};
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 3, 1).unwrap()
+    }
+
+    /// A finished game against CRANE, won on the last prediction if it is CRANE.
+    fn record(predictions: &[&str]) -> GameRecord {
+        let game = predictions.iter().fold(
+            Game::from_secret_word(word("CRANE")),
+            |game, &prediction| game.with_prediction(word(prediction)),
+        );
+        GameRecord::new(&game, date()).unwrap()
+    }
+
+    fn statistics_of(records: &[GameRecord]) -> Statistics {
+        Statistics {
+            records: records.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_records_only_finished_games() {
+        let game = Game::from_secret_word(word("CRANE")).with_prediction(word("SLOTH"));
+        assert!(GameRecord::new(&game, date()).is_none());
+
+        let won = record(&["SLOTH", "CRANE"]);
+        assert!(won.won);
+        assert_eq!(won.guesses, 2);
+        assert_eq!(won.answer, word("CRANE"));
+
+        let lost = record(&["SLOTH"; 6]);
+        assert!(!lost.won);
+        assert_eq!(lost.guesses, 6);
+    }
+
+    #[test]
+    fn test_no_games_played() {
+        let statistics = Statistics::default();
+        assert_eq!(statistics.games_played(), 0);
+        assert_eq!(statistics.win_percentage(), 0.0);
+        assert_eq!(statistics.current_streak(), 0);
+        assert_eq!(statistics.max_streak(), 0);
+        assert_eq!(statistics.guess_distribution().len(), 6);
+        assert!(statistics.guess_distribution().values().all(|&n| n == 0));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let won = record(&["CRANE"]);
+        let lost = record(&["SLOTH"; 6]);
+
+        let statistics = statistics_of(&[won.clone(), won.clone(), won.clone(), lost.clone(), won]);
+        assert_eq!(statistics.games_played(), 5);
+        assert_eq!(statistics.win_percentage(), 80.0);
+        assert_eq!(statistics.current_streak(), 1);
+        assert_eq!(statistics.max_streak(), 3);
+
+        let mut statistics = statistics;
+        statistics.record(lost);
+        assert_eq!(statistics.current_streak(), 0);
+        assert_eq!(statistics.max_streak(), 3);
+    }
+
+    #[test]
+    fn test_giving_up_resets_the_current_streak() {
+        let won = record(&["CRANE"]);
+        let mut statistics = statistics_of(&[won.clone(), won]);
+        assert_eq!(statistics.current_streak(), 2);
+
+        let given_up = Game::from_secret_word(word("CRANE"))
+            .with_prediction(word("SLOTH"))
+            .give_up();
+        let record = GameRecord::new(&given_up, date()).unwrap();
+        assert!(!record.won);
+        assert_eq!(record.guesses, 1);
+
+        statistics.record(record);
+        assert_eq!(statistics.games_played(), 3);
+        assert_eq!(statistics.current_streak(), 0);
+        assert_eq!(statistics.max_streak(), 2);
+    }
+
+    #[test]
+    fn test_guess_distribution() {
+        let statistics = statistics_of(&[
+            record(&["CRANE"]),
+            record(&["SLOTH", "CRANE"]),
+            record(&["SLOTH", "CRANE"]),
+            record(&["SLOTH"; 6]),
+        ]);
+
+        let distribution: Vec<usize> = statistics.guess_distribution().into_values().collect();
+        assert_eq!(distribution, vec![1, 2, 0, 0, 0, 0]);
+
+        // Games with more guesses widen the distribution
+        let long_win = Game::from_secret_word(word("CRANE"))
+            .with_maximum_guesses(None)
+            .with_prediction(word("SLOTH"));
+        let long_win = (0..7)
+            .fold(long_win, |game, _| game.with_prediction(word("BUMPY")))
+            .with_prediction(word("CRANE"));
+        let statistics = statistics_of(&[GameRecord::new(&long_win, date()).unwrap()]);
+        assert_eq!(statistics.guess_distribution().len(), 9);
+        assert_eq!(statistics.guess_distribution()[&9], 1);
+    }
+
+    #[test]
+    fn test_distribution_bars() {
+        let statistics = statistics_of(&[
+            record(&["CRANE"]),
+            record(&["SLOTH", "CRANE"]),
+            record(&["SLOTH", "CRANE"]),
+            record(&["SLOTH", "CRANE"]),
+        ]);
+
+        let bars = statistics.distribution_bars(10);
+        assert_eq!(bars.len(), 6);
+        assert_eq!(
+            bars[..3],
+            [
+                DistributionBar {
+                    guesses: 1,
+                    wins: 1,
+                    length: 4
+                },
+                DistributionBar {
+                    guesses: 2,
+                    wins: 3,
+                    length: 10
+                },
+                DistributionBar {
+                    guesses: 3,
+                    wins: 0,
+                    length: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let statistics = statistics_of(&[record(&["SLOTH", "CRANE"]), record(&["SLOTH"; 6])]);
+        assert_eq!(
+            statistics.to_string(),
+            "Played: 2
+Win %: 50
+Current streak: 0
+Max streak: 1
+Guess distribution:
+  1: 0
+  2: #################### 1
+  3: 0
+  4: 0
+  5: 0
+  6: 0"
+        );
+    }
+}
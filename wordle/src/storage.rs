@@ -0,0 +1,374 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::config::GameConfig;
+use crate::game::{Game, GameStatus};
+use crate::statistics::{GameRecord, Statistics};
+
+/// The directory where the player's games are saved: "$WORDLE_DATA_DIR" if it is set,
+/// otherwise "wordle" in the platform's data directory, e.g. "~/.local/share/wordle" on Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    match std::env::var_os("WORDLE_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::data_dir().map(|dir| dir.join("wordle")),
+    }
+}
+
+/// A game in progress, with the config it was started from so that it can be replayed.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SavedGame {
+    pub config: GameConfig,
+    pub game: Game,
+}
+
+/// A JSON file holding a [SavedGame], written again after every move.
+///
+/// ```rust
+/// use wordle::{Game, GameConfig, SaveFile, SavedGame};
+///
+/// let path = std::env::temp_dir().join(format!("wordle-doctest-{}.json", std::process::id()));
+/// let save_file = SaveFile::new(&path);
+///
+/// let config = GameConfig::default();
+/// let game = Game::new(&config).with_prediction("CRANE".parse().unwrap());
+/// save_file.save(&SavedGame { config, game }).unwrap();
+///
+/// let saved = save_file.load().unwrap().unwrap();
+/// assert_eq!(saved.game.plays().len(), 1);
+///
+/// save_file.delete().unwrap();
+/// assert!(save_file.load().unwrap().is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SaveFile {
+    path: PathBuf,
+}
+
+/// The contents of a data file, versioned so that older builds refuse newer files.
+#[derive(serde::Serialize)]
+struct Versioned<'a, T> {
+    version: u64,
+    #[serde(flatten)]
+    contents: &'a T,
+}
+
+/// Reads a JSON file written by [write_versioned], or `None` if there is no file.
+fn read_versioned<T: DeserializeOwned>(
+    path: &Path,
+    supported: u64,
+) -> Result<Option<T>, StorageError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(StorageError::Io(err)),
+    };
+
+    let mut value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|err| StorageError::Corrupt(err.to_string()))?;
+
+    let version = value
+        .as_object_mut()
+        .and_then(|fields| fields.remove("version"))
+        .and_then(|version| version.as_u64());
+    match version {
+        Some(version) if version == supported => {}
+        Some(found) => return Err(StorageError::UnsupportedVersion { found, supported }),
+        None => return Err(StorageError::Corrupt("missing version".to_string())),
+    }
+
+    let contents =
+        serde_json::from_value(value).map_err(|err| StorageError::Corrupt(err.to_string()))?;
+    Ok(Some(contents))
+}
+
+/// Writes contents as JSON with a format version.
+/// The file is written in full before it replaces the old one,
+/// so an interrupted write leaves the previous contents intact.
+fn write_versioned<T: Serialize>(
+    path: &Path,
+    version: u64,
+    contents: &T,
+) -> Result<(), StorageError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let json = serde_json::to_string_pretty(&Versioned { version, contents })
+        .map_err(|err| StorageError::Corrupt(err.to_string()))?;
+
+    let temporary_path = path.with_extension("tmp");
+    fs::write(&temporary_path, json)?;
+    fs::rename(&temporary_path, path)?;
+    Ok(())
+}
+
+/// Removes a file, if there is one.
+fn delete(path: &Path) -> Result<(), StorageError> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(StorageError::Io(err)),
+        _ => Ok(()),
+    }
+}
+
+impl SaveFile {
+    /// Version of the save file format written by this build.
+    pub const VERSION: u64 = 1;
+    /// Name of the file in the player's [data_dir], shared by the command line and the GUI.
+    pub const FILE_NAME: &'static str = "game.json";
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The player's save file, see [SaveFile::FILE_NAME].
+    /// There is none if the platform has no data directory.
+    pub fn default_location() -> Option<Self> {
+        data_dir().map(|dir| Self::new(dir.join(Self::FILE_NAME)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the saved game, or `None` if nothing was saved.
+    pub fn load(&self) -> Result<Option<SavedGame>, StorageError> {
+        read_versioned(&self.path, Self::VERSION)
+    }
+
+    /// Writes the game, replacing any game saved before.
+    pub fn save(&self, saved_game: &SavedGame) -> Result<(), StorageError> {
+        write_versioned(&self.path, Self::VERSION, saved_game)
+    }
+
+    /// Saves a game in progress, or deletes the save once the game has ended,
+    /// so that the next session starts a new game. A game with no plays yet isn't worth resuming.
+    pub fn update(&self, config: &GameConfig, game: &Game) -> Result<(), StorageError> {
+        if game.calculate_status() == GameStatus::Active && !game.plays().is_empty() {
+            self.save(&SavedGame {
+                config: config.clone(),
+                game: game.clone(),
+            })
+        } else {
+            self.delete()
+        }
+    }
+
+    /// Removes the saved game, if there is one.
+    pub fn delete(&self) -> Result<(), StorageError> {
+        delete(&self.path)
+    }
+}
+
+/// A JSON file holding the player's [Statistics], with a record added after every game.
+#[derive(Clone, Debug)]
+pub struct StatisticsFile {
+    path: PathBuf,
+}
+
+impl StatisticsFile {
+    /// Version of the statistics file format written by this build.
+    pub const VERSION: u64 = 1;
+    /// Name of the file in the player's [data_dir], shared by the command line and the GUI.
+    pub const FILE_NAME: &'static str = "statistics.json";
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The player's statistics file, see [StatisticsFile::FILE_NAME].
+    /// There is none if the platform has no data directory.
+    pub fn default_location() -> Option<Self> {
+        data_dir().map(|dir| Self::new(dir.join(Self::FILE_NAME)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the statistics, which are empty if no game was recorded yet.
+    pub fn load(&self) -> Result<Statistics, StorageError> {
+        read_versioned(&self.path, Self::VERSION).map(Option::unwrap_or_default)
+    }
+
+    pub fn save(&self, statistics: &Statistics) -> Result<(), StorageError> {
+        write_versioned(&self.path, Self::VERSION, statistics)
+    }
+
+    /// Adds a game which has ended, dated today, and returns the updated statistics.
+    /// A game still in progress is not recorded.
+    ///
+    /// Statistics which can't be read are left as they are, rather than replaced.
+    pub fn record(&self, game: &Game) -> Result<Statistics, StorageError> {
+        let mut statistics = self.load()?;
+        if let Some(record) = GameRecord::new(game, Local::now().date_naive()) {
+            statistics.record(record);
+            self.save(&statistics)?;
+        }
+        Ok(statistics)
+    }
+}
+
+/// Reasons a saved game or statistics can't be read or written.
+#[derive(Debug)]
+pub enum StorageError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file isn't valid, e.g. it was edited or only partly written.
+    Corrupt(String),
+    /// The file was written by another version of the game.
+    UnsupportedVersion { found: u64, supported: u64 },
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to access saved data: {}", err),
+            Self::Corrupt(msg) => write!(f, "Saved data is corrupt: {}", msg),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "Saved data has format version {}, but only version {} is supported.",
+                found, supported
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::{AnswerSelection, Game, GameConfig, GameStatus, Puzzle};
+
+    use super::{SaveFile, StatisticsFile, StorageError};
+
+    /// A save file in a fresh temporary directory, unique to the test.
+    fn save_file(test_name: &str) -> SaveFile {
+        let dir: PathBuf = std::env::temp_dir().join(format!(
+            "wordle-storage-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        SaveFile::new(dir.join("game.json"))
+    }
+
+    #[test]
+    fn test_saved_game_resumes() {
+        let save_file = save_file("resumes");
+        assert!(save_file.load().unwrap().is_none());
+
+        let config = GameConfig::default().with_answer(AnswerSelection::Seeded(7));
+        let game = Game::new(&config).with_prediction("CRANE".parse().unwrap());
+        save_file.update(&config, &game).unwrap();
+
+        let saved = save_file.load().unwrap().unwrap();
+        assert_eq!(saved.config.answer, AnswerSelection::Seeded(7));
+        assert_eq!(saved.game.puzzle, Puzzle::Seeded(7));
+        assert_eq!(saved.game.secret_word(), game.secret_word());
+        assert_eq!(saved.game.plays().len(), 1);
+        assert_eq!(saved.game.constraints(), game.constraints());
+    }
+
+    #[test]
+    fn test_finished_game_is_deleted() {
+        let save_file = save_file("finished");
+        let config = GameConfig::default().with_answer(AnswerSelection::Seeded(7));
+        let game = Game::new(&config).with_prediction("CRANE".parse().unwrap());
+        save_file.update(&config, &game).unwrap();
+        assert!(save_file.path().exists());
+
+        let game = game.with_prediction(game.secret_word().clone());
+        assert!(game.calculate_status() == GameStatus::Won);
+        save_file.update(&config, &game).unwrap();
+        assert!(!save_file.path().exists());
+        assert!(save_file.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupt_and_outdated_files_are_reported() {
+        let save_file = save_file("corrupt");
+        let config = GameConfig::default();
+        let game = Game::new(&config).with_prediction("CRANE".parse().unwrap());
+        save_file.update(&config, &game).unwrap();
+
+        // A file cut off part way through
+        let contents = fs::read_to_string(save_file.path()).unwrap();
+        fs::write(save_file.path(), &contents[..contents.len() / 2]).unwrap();
+        assert!(matches!(save_file.load(), Err(StorageError::Corrupt(_))));
+
+        let newer = contents.replace("\"version\": 1", "\"version\": 2");
+        fs::write(save_file.path(), newer).unwrap();
+        let err = save_file.load().unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Saved data has format version 2, but only version 1 is supported."
+        );
+
+        fs::write(save_file.path(), "{\"config\": {}}").unwrap();
+        assert!(matches!(save_file.load(), Err(StorageError::Corrupt(_))));
+
+        // Saving again replaces the bad file
+        save_file.update(&config, &game).unwrap();
+        assert!(save_file.load().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_statistics_are_recorded() {
+        let statistics_file = StatisticsFile::new(save_file("statistics").path());
+        assert_eq!(statistics_file.load().unwrap().games_played(), 0);
+
+        let game = Game::from_secret_word("CRANE".parse().unwrap());
+        let statistics = statistics_file.record(&game).unwrap();
+        assert_eq!(statistics.games_played(), 0);
+        assert!(!statistics_file.path().exists());
+
+        let won = game.with_prediction("CRANE".parse().unwrap());
+        statistics_file.record(&won).unwrap();
+        let statistics = statistics_file.record(&won).unwrap();
+        assert_eq!(statistics.games_played(), 2);
+        assert_eq!(statistics_file.load().unwrap(), statistics);
+        assert_eq!(statistics.current_streak(), 2);
+    }
+
+    #[test]
+    fn test_corrupt_statistics_are_kept() {
+        let statistics_file = StatisticsFile::new(save_file("corrupt-statistics").path());
+        fs::create_dir_all(statistics_file.path().parent().unwrap()).unwrap();
+        fs::write(
+            statistics_file.path(),
+            "{\"version\": 1, \"records\": [{}]}",
+        )
+        .unwrap();
+
+        let won = Game::from_secret_word("CRANE".parse().unwrap())
+            .with_prediction("CRANE".parse().unwrap());
+        assert!(matches!(
+            statistics_file.record(&won),
+            Err(StorageError::Corrupt(_))
+        ));
+        assert_eq!(
+            fs::read_to_string(statistics_file.path()).unwrap(),
+            "{\"version\": 1, \"records\": [{}]}"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rayon::prelude::*;
+
+use crate::dictionary::Dictionary;
+use crate::game::{Difficulty, Game, GameStatus};
+use crate::solver::Solver;
+use crate::word::Word;
+
+/// Chooses the guesses for a game, such as a [Solver].
+pub trait Strategy {
+    /// The next word to play, or nothing to give up.
+    fn next_guess(&self, game: &Game) -> Option<Word>;
+}
+
+impl Strategy for Solver {
+    fn next_guess(&self, game: &Game) -> Option<Word> {
+        self.best_guess(game)
+    }
+}
+
+/// Plays a game to the end with a strategy.
+/// Stops early if the strategy gives up or breaks the rules of the game's difficulty.
+/// With unlimited guesses, the game only ends once the strategy wins or gives up.
+pub fn play<S: Strategy + ?Sized>(strategy: &S, game: Game) -> Game {
+    let mut game = game;
+
+    while game.calculate_status() == GameStatus::Active {
+        let Some(guess) = strategy.next_guess(&game) else {
+            break;
+        };
+
+        match game.try_with_prediction(guess) {
+            Ok(new_game) => game = new_game,
+            Err(_) => break,
+        }
+    }
+
+    game
+}
+
+/// How a strategy did against one answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub answer: Word,
+    pub guesses: usize,
+    pub won: bool,
+}
+
+/// Results of playing a strategy against every answer in a dictionary.
+///
+/// ```rust
+/// use wordle::{Benchmark, Difficulty, Dictionary, Solver};
+///
+/// let answers = "CATCH\nHATCH\nMATCH\nPATCH\n";
+/// let allowed = "CHOMP\n";
+/// let dictionary = Dictionary::from_readers(answers.as_bytes(), allowed.as_bytes()).unwrap();
+/// let solver = Solver::new(&dictionary);
+///
+/// let benchmark = Benchmark::run(&solver, &dictionary, Difficulty::Normal);
+///
+/// // CHOMP first, then the answer
+/// assert_eq!(benchmark.average_guesses(), 2.0);
+/// assert!(benchmark.failures().is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Benchmark {
+    /// One outcome per answer, in dictionary order.
+    pub outcomes: Vec<Outcome>,
+}
+
+impl Benchmark {
+    /// Plays one game against each answer in the dictionary, in parallel.
+    pub fn run<S: Strategy + Sync + ?Sized>(
+        strategy: &S,
+        dictionary: &Dictionary,
+        difficulty: Difficulty,
+    ) -> Self {
+        let outcomes = dictionary
+            .answers()
+            .par_iter()
+            .map(|answer| {
+                let game = Game::from_secret_word(answer.clone()).with_difficulty(difficulty);
+                let game = play(strategy, game);
+
+                Outcome {
+                    answer: answer.clone(),
+                    guesses: game.plays().len(),
+                    won: game.calculate_status() == GameStatus::Won,
+                }
+            })
+            .collect();
+
+        Self { outcomes }
+    }
+
+    /// Average number of guesses of the games won.
+    pub fn average_guesses(&self) -> f64 {
+        let won: Vec<&Outcome> = self.outcomes.iter().filter(|outcome| outcome.won).collect();
+        if won.is_empty() {
+            return 0.0;
+        }
+
+        let total: usize = won.iter().map(|outcome| outcome.guesses).sum();
+        total as f64 / won.len() as f64
+    }
+
+    /// Number of games won with each number of guesses.
+    pub fn histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for outcome in self.outcomes.iter().filter(|outcome| outcome.won) {
+            *histogram.entry(outcome.guesses).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// The answers which were not found within [crate::GameConfig::DEFAULT_MAXIMUM_GUESSES].
+    pub fn failures(&self) -> Vec<&Word> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| !outcome.won)
+            .map(|outcome| &outcome.answer)
+            .collect()
+    }
+
+    /// The hardest answers for the strategy: failures first, then by most guesses.
+    pub fn worst(&self, count: usize) -> Vec<&Outcome> {
+        let mut outcomes: Vec<&Outcome> = self.outcomes.iter().collect();
+        outcomes.sort_by_key(|outcome| (outcome.won, Reverse(outcome.guesses)));
+        outcomes.truncate(count);
+        outcomes
+    }
+}
+
+impl fmt::Display for Benchmark {
+    /// A report for comparing strategies, e.g. in CI logs.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Only list a few words, there may be thousands of answers
+        const MAXIMUM_LISTED: usize = 10;
+
+        let games = self.outcomes.len();
+        let failures = self.failures();
+
+        writeln!(f, "Games: {}", games)?;
+        writeln!(f, "Won: {}", games - failures.len())?;
+        writeln!(f, "Average guesses: {:.3}", self.average_guesses())?;
+
+        writeln!(f, "Guesses:")?;
+        for (guesses, count) in self.histogram() {
+            writeln!(f, "  {}: {}", guesses, count)?;
+        }
+
+        write!(f, "Failures: {}", failures.len())?;
+        for word in failures.iter().take(MAXIMUM_LISTED) {
+            write!(f, " {}", word)?;
+        }
+        writeln!(f)?;
+
+        write!(f, "Worst:")?;
+        for outcome in self.worst(MAXIMUM_LISTED) {
+            write!(f, " {} ({})", outcome.answer, outcome.guesses)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::word;
+    use crate::{Dictionary, Difficulty, Game, GameConfig, Solver, Word};
+
+    use super::{play, Benchmark, Strategy};
+
+    fn dictionary() -> Dictionary {
+        let answers = "CATCH\nHATCH\nMATCH\nPATCH\nWATCH\n";
+        Dictionary::from_readers(answers.as_bytes(), "CHOMP\n".as_bytes()).unwrap()
+    }
+
+    /// Plays the same word every turn.
+    struct Stubborn(Word);
+
+    impl Strategy for Stubborn {
+        fn next_guess(&self, _: &Game) -> Option<Word> {
+            Some(self.0.clone())
+        }
+    }
+
+    /// Gives up straight away.
+    struct Quitter;
+
+    impl Strategy for Quitter {
+        fn next_guess(&self, _: &Game) -> Option<Word> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_play_stops_when_game_ends() {
+        let won = play(
+            &Stubborn(word("HATCH")),
+            Game::from_secret_word(word("HATCH")),
+        );
+        assert_eq!(won.plays().len(), 1);
+
+        let lost = play(
+            &Stubborn(word("HATCH")),
+            Game::from_secret_word(word("MATCH")),
+        );
+        assert_eq!(lost.plays().len(), GameConfig::DEFAULT_MAXIMUM_GUESSES);
+
+        let abandoned = play(&Quitter, Game::from_secret_word(word("MATCH")));
+        assert!(abandoned.plays().is_empty());
+    }
+
+    #[test]
+    fn test_benchmark_counts_failures() {
+        let benchmark = Benchmark::run(&Stubborn(word("HATCH")), &dictionary(), Difficulty::Normal);
+
+        assert_eq!(benchmark.outcomes.len(), 5);
+        assert_eq!(benchmark.average_guesses(), 1.0);
+        assert_eq!(
+            benchmark.histogram().into_iter().collect::<Vec<_>>(),
+            [(1, 1)]
+        );
+        assert_eq!(
+            benchmark.failures(),
+            [
+                &word("CATCH"),
+                &word("MATCH"),
+                &word("PATCH"),
+                &word("WATCH")
+            ]
+        );
+
+        let worst = benchmark.worst(2);
+        assert_eq!(worst[0].answer, word("CATCH"));
+        assert_eq!(worst[1].answer, word("MATCH"));
+        assert!(!worst[0].won);
+    }
+
+    #[test]
+    fn test_benchmark_solver() {
+        let dictionary = dictionary();
+        let solver = Solver::new(&dictionary);
+
+        for difficulty in [Difficulty::Normal, Difficulty::Hard] {
+            let benchmark = Benchmark::run(&solver, &dictionary, difficulty);
+
+            assert!(benchmark.failures().is_empty());
+            assert_eq!(benchmark.histogram().values().sum::<usize>(), 5);
+            assert_eq!(benchmark.worst(1)[0].guesses, 3);
+        }
+    }
+}
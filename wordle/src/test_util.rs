@@ -0,0 +1,15 @@
+//! Helpers shared by the unit tests.
+
+use crate::{Game, Word};
+
+/// Parses a word, which the test knows to be valid.
+pub(crate) fn word(s: &str) -> Word {
+    s.parse().unwrap()
+}
+
+/// Plays each of the predictions in turn.
+pub(crate) fn game_with_predictions(game: Game, predictions: &[&str]) -> Game {
+    predictions.iter().fold(game, |game, &prediction| {
+        game.with_prediction(word(prediction))
+    })
+}
@@ -3,7 +3,7 @@ use std::str::Chars;
 use std::str::FromStr;
 
 /// The player's score for one letter of a guess.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum LetterScore {
     /// The correct letter was guessed in the correct spot.
     PlacedCorrectly,
@@ -41,7 +41,7 @@ impl fmt::Display for LetterScore {
 /// assert_eq!(format!("{}", winning_score), "XXXXX");
 /// assert!(winning_score.is_winner());
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct WordScore(pub Vec<LetterScore>);
 
 impl WordScore {
@@ -61,10 +61,62 @@ impl fmt::Display for WordScore {
     }
 }
 
+#[derive(Clone, Debug)]
+pub enum ScoreParseError {
+    InvalidLength,
+    InvalidCharacters,
+}
+
+impl fmt::Display for ScoreParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(
+                f,
+                "Score must be {} to {} letters long.",
+                Word::MIN_LENGTH,
+                Word::MAX_LENGTH
+            ),
+            Self::InvalidCharacters => write!(f, "Score must contain only X, O and _."),
+        }
+    }
+}
+
+/// implements [str::parse::<WordScore>], reading the notation written by [fmt::Display]
+///
+/// ```rust
+/// # use wordle::word::{LetterScore, WordScore};
+/// let score: WordScore = "_x__O".parse().unwrap();
+///
+/// assert_eq!(score.0[1], LetterScore::PlacedCorrectly);
+/// assert_eq!(score.to_string(), "_X__O");
+/// ```
+impl FromStr for WordScore {
+    type Err = ScoreParseError;
+
+    /// Letters are case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !(Word::MIN_LENGTH..=Word::MAX_LENGTH).contains(&s.chars().count()) {
+            return Err(ScoreParseError::InvalidLength);
+        }
+
+        let letter_scores = s
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'X' => Ok(LetterScore::PlacedCorrectly),
+                'O' => Ok(LetterScore::PresentElsewhere),
+                '_' => Ok(LetterScore::NotPresent),
+                _ => Err(ScoreParseError::InvalidCharacters),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(WordScore(letter_scores))
+    }
+}
+
 /// A legal word according to the rules of wordle. A newtype adding constraints on [String].
 ///
 /// This type guarantees that:
-/// * The word is exactly five letters long.
+/// * The word is between [Word::MIN_LENGTH] and [Word::MAX_LENGTH] letters long.
 /// * The word contains only letters from the English alphabet.
 /// * The letters are stored as uppercase.
 ///
@@ -76,56 +128,134 @@ impl fmt::Display for WordScore {
 /// // Words are normalized to uppercase
 /// assert_eq!(adieu.to_string(), "ADIEU");
 ///
+/// // Words of different lengths are allowed, for variants of the game
+/// assert_eq!("Sphinx".parse::<Word>().unwrap().length(), 6);
+///
 /// // Invalid words are not allowed
-/// let invalid_word: Result<Word, WordParseError> = "onomatopeia".parse::<Word>();
+/// let invalid_word: Result<Word, WordParseError> = "onomatopoeia".parse::<Word>();
 /// assert!(invalid_word.is_err());
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Word(String);
 
 impl Word {
+    /// Length of the shortest words the game can be played with.
+    pub const MIN_LENGTH: usize = 4;
+    /// Length of the longest words the game can be played with.
+    pub const MAX_LENGTH: usize = 11;
+
+    /// Creates a word from letters known to be valid, such as the built-in word lists
+    /// checked by the build script.
+    pub(crate) fn from_validated(letters: &str) -> Self {
+        debug_assert!(letters.parse::<Word>().is_ok());
+        Word(letters.to_uppercase())
+    }
+
     /// Returns an iterator over the letters of the word.
-    pub fn letters(&self) -> Chars {
+    pub fn letters(&self) -> Chars<'_> {
         self.0.chars()
     }
 
-    /// Compares two letters at a given position resulting in a [LetterScore].
-    fn compare_letter(&self, position: usize, letter: char) -> LetterScore {
-        if self.letters().nth(position) == Some(letter) {
-            LetterScore::PlacedCorrectly
-        } else if self.letters().any(|c| c == letter) {
-            LetterScore::PresentElsewhere
-        } else {
-            LetterScore::NotPresent
-        }
+    /// Number of letters in the word.
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The letters of the word as uppercase ASCII bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
     }
 
     /// Compares a prediction against the secret word, resulting in a [WordScore].
+    /// Both words should be the same length; see [crate::Game::try_with_prediction].
+    ///
+    /// Scoring takes two passes so that repeated letters are not over-counted:
+    /// letters in the right spot are marked first, then the remaining letters are marked
+    /// [LetterScore::PresentElsewhere] only while the secret word has unmatched copies left.
+    ///
+    /// ```rust
+    /// # use wordle::Word;
+    /// let secret: Word = "ABIDE".parse().unwrap();
+    /// let prediction: Word = "SPEED".parse().unwrap();
+    ///
+    /// // Only one of the E's is marked, because ABIDE has only one E.
+    /// assert_eq!(secret.guess(&prediction).to_string(), "__O_O");
+    /// ```
     pub fn guess(&self, prediction: &Self) -> WordScore {
-        // Guess each letter then collect the result
-        let letter_scores = prediction
-            .letters()
-            .enumerate()
-            .map(|(position, letter)| self.compare_letter(position, letter));
+        let mut letter_scores = vec![LetterScore::NotPresent; prediction.0.len()];
+
+        // Letters of the secret word which are not matched by a correctly placed letter,
+        // counted by position in the alphabet.
+        let mut unmatched = [0usize; 26];
+
+        // First pass: letters in the correct spot
+        for (position, (secret, guessed)) in self.letters().zip(prediction.letters()).enumerate() {
+            if secret == guessed {
+                letter_scores[position] = LetterScore::PlacedCorrectly;
+            } else {
+                unmatched[alphabet_index(secret)] += 1;
+            }
+        }
+
+        // Second pass: letters elsewhere in the word, while unmatched copies remain
+        for (position, guessed) in prediction.letters().enumerate() {
+            let count = &mut unmatched[alphabet_index(guessed)];
+            if letter_scores[position] != LetterScore::PlacedCorrectly && *count > 0 {
+                letter_scores[position] = LetterScore::PresentElsewhere;
+                *count -= 1;
+            }
+        }
+
+        WordScore(letter_scores)
+    }
+}
 
-        WordScore(letter_scores.collect())
+/// Spells out a word length, e.g. "five", so that messages read naturally.
+pub(crate) fn number_name(n: usize) -> String {
+    const NAMES: [&str; 12] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven",
+    ];
+    match NAMES.get(n) {
+        Some(name) => name.to_string(),
+        None => n.to_string(),
     }
 }
 
+/// Position of an uppercase letter in the English alphabet, starting from zero.
+fn alphabet_index(letter: char) -> usize {
+    (letter as u8 - b'A') as usize
+}
+
 #[derive(Clone, Debug)]
 pub enum WordParseError {
+    /// The word is shorter than [Word::MIN_LENGTH] or longer than [Word::MAX_LENGTH].
     InvalidLength,
+    /// The word is not the length of the words being played.
+    WrongLength {
+        expected: usize,
+    },
     InvalidCharacters,
+    NotInWordList,
 }
 
 impl fmt::Display for WordParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidLength => write!(f, "Word must be five letters long."),
+            Self::InvalidLength => write!(
+                f,
+                "Word must be {} to {} letters long.",
+                Word::MIN_LENGTH,
+                Word::MAX_LENGTH
+            ),
+            Self::WrongLength { expected } => {
+                write!(f, "Word must be {} letters long.", number_name(*expected))
+            }
             Self::InvalidCharacters => write!(
                 f,
                 "Word must contain only letters from the English alphabet."
             ),
+            Self::NotInWordList => write!(f, "Word is not in the word list."),
         }
     }
 }
@@ -137,7 +267,7 @@ impl FromStr for Word {
     /// Validates and creates a [Word] at runtime.
     /// Normalizes to uppercase, so words have only one representation.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.chars().count() != 5 {
+        if !(Word::MIN_LENGTH..=Word::MAX_LENGTH).contains(&s.chars().count()) {
             Err(WordParseError::InvalidLength)
         } else if s.chars().any(|c| !c.is_ascii_alphabetic()) {
             Err(WordParseError::InvalidCharacters)
@@ -161,6 +291,40 @@ impl From<Word> for String {
     }
 }
 
+/// Serializes a word as its string, e.g. `"CRANE"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Word {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Deserializes a word from its string, checked like [str::parse::<Word>].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Word {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes a score in the notation of [fmt::Display], e.g. `"XO_OO"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WordScore {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a score from the notation of [fmt::Display], see [str::parse::<WordScore>].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WordScore {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::LetterScore;
@@ -199,6 +363,27 @@ mod tests {
         assert_eq!(format!("{}", WordScore(vec![O, X, O, O, U])), "OXOO_");
     }
 
+    #[test]
+    fn parses_word_score() {
+        let table = [
+            ("XO_OO", WordScore(vec![X, O, U, O, O])),
+            ("__xxx", WordScore(vec![U, U, X, X, X])),
+            ("oxoo_", WordScore(vec![O, X, O, O, U])),
+        ];
+
+        for (s, expected) in table {
+            assert_eq!(s.parse::<WordScore>().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_word_score() {
+        assert!("XO_".parse::<WordScore>().is_err());
+        assert!("XO_OOXXO_OOX".parse::<WordScore>().is_err());
+        assert!("XO-OO".parse::<WordScore>().is_err());
+        assert!("GYGYG".parse::<WordScore>().is_err());
+    }
+
     use super::Word;
 
     #[test]
@@ -224,7 +409,36 @@ mod tests {
 
     #[test]
     fn rejects_long_word_from_string() {
-        assert!("PRAIRIE".parse::<Word>().is_err());
+        assert!("PRESTIGIOUS".parse::<Word>().is_ok());
+        assert!("PRESTIDIGITATOR".parse::<Word>().is_err());
+    }
+
+    #[test]
+    fn accepts_words_of_every_supported_length() {
+        for word in ["LION", "PRAIRIE", "HOUSEBOATS"] {
+            assert_eq!(word.parse::<Word>().unwrap().length(), word.len());
+        }
+    }
+
+    #[test]
+    fn scores_longer_words() {
+        let secret = "PRAIRIE".parse::<Word>().unwrap();
+        let prediction = "PAIRING".parse::<Word>().unwrap();
+        assert_eq!(secret.guess(&prediction).to_string(), "XOOOO__");
+    }
+
+    #[test]
+    fn formats_length_errors() {
+        use super::WordParseError;
+
+        assert_eq!(
+            WordParseError::WrongLength { expected: 5 }.to_string(),
+            "Word must be five letters long."
+        );
+        assert_eq!(
+            WordParseError::InvalidLength.to_string(),
+            "Word must be 4 to 11 letters long."
+        );
     }
 
     #[test]
@@ -265,4 +479,89 @@ mod tests {
         let guess = word.guess(&wrong_guess);
         assert_eq!(format!("{}", guess), "_X__O");
     }
+
+    #[test]
+    fn scores_repeated_letters() {
+        // (secret word, prediction, score)
+        let table = [
+            // Repeated letters in the prediction only
+            ("ABIDE", "SPEED", "__O_O"),
+            ("ABIDE", "EERIE", "___OX"),
+            ("CRANE", "EERIE", "__O_X"),
+            ("HUMPH", "SASSY", "_____"),
+            ("CIGAR", "ALLAY", "___X_"),
+            ("SPICE", "SISSY", "XO___"),
+            ("BENCH", "EERIE", "_X___"),
+            // Repeated letters in the secret word only
+            ("EERIE", "ABIDE", "__O_X"),
+            ("SISSY", "SPICE", "X_O__"),
+            ("SISSY", "STRAY", "X___X"),
+            ("ABATE", "CIGAR", "___O_"),
+            ("HUMPH", "HEATH", "X___X"),
+            // Repeated letters in both
+            ("EERIE", "EERIE", "XXXXX"),
+            ("SISSY", "SISSY", "XXXXX"),
+            ("SISSY", "ASSES", "_OX_O"),
+            ("EERIE", "GEESE", "_XO_X"),
+            ("EERIE", "EMCEE", "X__OX"),
+            ("ABATE", "BANAL", "OO_O_"),
+            ("KARMA", "ALPHA", "O___X"),
+            ("NAVAL", "BANAL", "_XOXX"),
+            ("EVADE", "EERIE", "X___X"),
+            ("SISSY", "MISSY", "_XXXX"),
+            ("HUMPH", "HUMPH", "XXXXX"),
+            ("AWAKE", "ABATE", "X_X_X"),
+        ];
+
+        for (secret, prediction, expected) in table {
+            let secret = secret.parse::<Word>().unwrap();
+            let prediction = prediction.parse::<Word>().unwrap();
+            assert_eq!(
+                secret.guess(&prediction).to_string(),
+                expected,
+                "{} guessed against {}",
+                prediction,
+                secret
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_words_and_scores_as_strings() {
+        use super::Word;
+
+        let word: Word = "crane".parse().unwrap();
+        let json = serde_json::to_string(&word).unwrap();
+        assert_eq!(json, "\"CRANE\"");
+        assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+
+        let score = WordScore(vec![X, O, U, O, O]);
+        let json = serde_json::to_string(&score).unwrap();
+        assert_eq!(json, "\"XO_OO\"");
+        assert_eq!(serde_json::from_str::<WordScore>(&json).unwrap(), score);
+
+        // Lowercase is accepted, as when parsing
+        assert_eq!(serde_json::from_str::<Word>("\"crane\"").unwrap(), word);
+        assert_eq!(
+            serde_json::from_str::<WordScore>("\"xo_oo\"").unwrap(),
+            score
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rejects_invalid_serialized_words_and_scores() {
+        use super::Word;
+
+        let err = serde_json::from_str::<Word>("\"CR4NE\"").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Word must contain only letters from the English alphabet."));
+
+        assert!(serde_json::from_str::<Word>("\"ONOMATOPOEIA\"").is_err());
+        assert!(serde_json::from_str::<Word>("5").is_err());
+        assert!(serde_json::from_str::<WordScore>("\"XO?OO\"").is_err());
+        assert!(serde_json::from_str::<WordScore>("\"XO\"").is_err());
+    }
 }